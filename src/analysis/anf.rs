@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::fmt;
+use crate::ex03::ast::{expr_to_infix_string, ast_to_string, parse_formula, ASTNode, Expr, Notation, Op};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::bitset::TruthTable;
+
+// 대수 정규형(ANF, Zhegalkin 다항식): 변수들의 AND(단항식)를 XOR로 연결한 식
+// 모든 불 함수는 ANF 표현이 하나뿐이며, 진리표에 뫼비우스 변환을 하면 단항식 계수를 얻음
+// 계수표의 행 u는 u의 비트가 1인 변수들의 단항식 (첫 번째 변수가 최상위 비트, 행 0은 상수 1)
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Anf {
+    coefficients: TruthTable,
+}
+
+impl Anf {
+    pub fn from_ast(ast: &Expr) -> Result<Anf, FormulaError> {
+        Ok(Anf::from_table(&TruthTable::from_ast(ast)?))
+    }
+
+    pub fn from_table(table: &TruthTable) -> Anf {
+        Anf { coefficients: table.mobius() }
+    }
+
+    // 단항식 계수로 만듦. coefficients의 행 u가 1이면 단항식 u가 포함됨
+    pub fn from_coefficients(coefficients: TruthTable) -> Anf {
+        Anf { coefficients }
+    }
+
+    pub fn vars(&self) -> &[Symbol] {
+        self.coefficients.vars()
+    }
+
+    pub fn coefficients(&self) -> &TruthTable {
+        &self.coefficients
+    }
+
+    // 포함된 단항식의 행 번호. 차수가 낮은 것부터, 같은 차수는 앞쪽 변수가 먼저
+    pub fn monomials(&self) -> Vec<usize> {
+        let mut monomials: Vec<usize> = set_rows(&self.coefficients).collect();
+        monomials.sort_by_key(|&monomial| (monomial.count_ones(), Reverse(monomial)));
+        monomials
+    }
+
+    // 대수적 차수: 가장 큰 단항식의 변수 개수. 상수 함수는 0
+    pub fn degree(&self) -> usize {
+        set_rows(&self.coefficients).map(|monomial| monomial.count_ones() as usize).max().unwrap_or(0)
+    }
+
+    // 뫼비우스 변환은 자기 역변환이므로 같은 변환으로 진리표를 되돌림
+    pub fn to_table(&self) -> TruthTable {
+        self.coefficients.mobius()
+    }
+
+    // 단항식들의 XOR. 중위 표기에서 "1 ^ A ^ A & B"처럼 읽히도록 왼쪽으로 중첩
+    pub fn to_expr(&self) -> Expr {
+        self.monomials()
+            .into_iter()
+            .map(|monomial| self.monomial_expr(monomial))
+            .reduce(|acc, term| Expr::binary(Op::Xor, acc, term))
+            .unwrap_or(Expr::Const(false))
+    }
+
+    fn monomial_expr(&self, monomial: usize) -> Expr {
+        let n = self.vars().len();
+        self.vars()
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| monomial >> (n - 1 - i) & 1 == 1)
+            .map(|(_, &var)| Expr::Var(var))
+            .reduce(Expr::and)
+            .unwrap_or(Expr::Const(true))
+    }
+}
+
+impl fmt::Display for Anf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", expr_to_infix_string(&self.to_expr()))
+    }
+}
+
+// 1인 행 번호를 차례로
+fn set_rows(table: &TruthTable) -> impl Iterator<Item = usize> + '_ {
+    table.words().iter().enumerate().flat_map(|(index, &word)| {
+        let mut word = word;
+        std::iter::from_fn(move || {
+            if word == 0 {
+                return None;
+            }
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+            Some(index * 64 + bit)
+        })
+    })
+}
+
+// 식을 ANF로 변환
+pub fn anf(ast: &Expr) -> Result<Expr, FormulaError> {
+    Ok(Anf::from_ast(ast)?.to_expr())
+}
+
+// 기존 ASTNode 트리에 대한 ANF 변환. 결과도 ASTNode
+pub fn anf_node(node: &ASTNode) -> Result<ASTNode, FormulaError> {
+    ASTNode::try_from(anf(&Expr::try_from(node)?)?)
+}
+
+pub fn algebraic_degree(ast: &Expr) -> Result<usize, FormulaError> {
+    Ok(Anf::from_ast(ast)?.degree())
+}
+
+// 식이 ANF 모양(양의 리터럴 단항식 또는 1의 XOR, 또는 상수 0)인지 확인하는 함수
+pub fn is_anf(ast: &Expr) -> bool {
+    match ast {
+        Expr::Const(false) => true,
+        _ => is_xor_of_monomials(ast),
+    }
+}
+
+fn is_xor_of_monomials(ast: &Expr) -> bool {
+    match ast {
+        Expr::Binary(Op::Xor, left, right) => is_xor_of_monomials(left) && is_xor_of_monomials(right),
+        Expr::Const(true) => true,
+        _ => is_monomial(ast),
+    }
+}
+
+fn is_monomial(ast: &Expr) -> bool {
+    match ast {
+        Expr::Binary(Op::And, left, right) => is_monomial(left) && is_monomial(right),
+        Expr::Var(_) => true,
+        _ => false,
+    }
+}
+
+/// 주어진 표기법으로 읽고, 같은 표기법으로 ANF를 반환
+pub fn try_algebraic_normal_form(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&anf(&ast)?, notation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    fn anf_of(formula: &str) -> String {
+        try_algebraic_normal_form(formula, Notation::Postfix).unwrap()
+    }
+
+    #[test]
+    fn test_anf_conversion() {
+        assert_eq!(anf_of("AB|"), "AB^AB&^");
+        assert_eq!(anf_of("AB>"), "1A^AB&^");
+        assert_eq!(anf_of("AB="), "1A^B^");
+        assert_eq!(anf_of("A!"), "1A^");
+        assert_eq!(anf_of("AB&C|"), "CAB&^AB&C&^");
+        assert_eq!(anf_of("AA!&"), "0");
+        assert_eq!(anf_of("AA!|"), "1");
+        assert_eq!(try_algebraic_normal_form("A > B", Notation::Infix).unwrap(), "1 ^ A ^ A & B");
+        assert_eq!(try_algebraic_normal_form("A&", Notation::Postfix), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+
+        let node = ASTNode::Operator('|', Box::new(ASTNode::Operand('A')), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(Expr::try_from(anf_node(&node).unwrap()).unwrap(), parse_expr("AB^AB&^").unwrap());
+
+        // ANF의 ANF는 자기 자신이며, 진리표로 되돌리면 원래 함수
+        for formula in ["AB&C|", "AB>C=D^", "ABC^^!", "AB|CD&>E|", "AA!&", "1"] {
+            let ast = parse_expr(formula).unwrap();
+            let anf_ast = anf(&ast).unwrap();
+            assert!(is_anf(&anf_ast), "{}", formula);
+            assert_eq!(anf(&anf_ast).unwrap(), anf_ast, "{}", formula);
+            let table = TruthTable::from_ast(&ast).unwrap();
+            let result = Anf::from_table(&table);
+            assert_eq!(result.to_table(), table, "{}", formula);
+            if !table.vars().is_empty() {
+                assert_eq!(TruthTable::from_ast_with_vars(&anf_ast, table.vars()).unwrap(), table, "{}", formula);
+            }
+        }
+        assert!(!is_anf(&parse_expr("AB|").unwrap()));
+        assert!(!is_anf(&parse_expr("A!B^").unwrap()));
+    }
+
+    #[test]
+    fn test_degree() {
+        let degree = |formula: &str| algebraic_degree(&parse_expr(formula).unwrap()).unwrap();
+        assert_eq!(degree("AA!|"), 0);
+        assert_eq!(degree("AB^C^"), 1);
+        assert_eq!(degree("AB&C^"), 2);
+        assert_eq!(degree("AB|C|"), 3);
+        // 다수결 함수: AB ^ AC ^ BC
+        assert_eq!(degree("AB&AC&|BC&|"), 2);
+        assert_eq!(Anf::from_ast(&parse_expr("AB&AC&|BC&|").unwrap()).unwrap().to_string(), "A & B ^ A & C ^ B & C");
+
+        // 단어 경계를 넘는 변수 8개: 모든 변수의 AND는 최고 차수 단항식 하나
+        let all = parse_expr("ABCDEFGH&&&&&&&").unwrap();
+        let result = Anf::from_ast(&all).unwrap();
+        assert_eq!(result.monomials(), vec![255]);
+        assert_eq!(result.degree(), 8);
+        let or = Anf::from_ast(&parse_expr("ABCDEFGH|||||||").unwrap()).unwrap();
+        assert_eq!(or.monomials().len(), 255);
+        assert_eq!(or.degree(), 8);
+
+        let vars: Vec<Symbol> = "AB".chars().map(Symbol::from).collect();
+        let parity = Anf::from_coefficients(TruthTable::from_bools(&vars, &[true, true, true, false]).unwrap());
+        assert_eq!(parity.to_expr(), parse_expr("1A^B^").unwrap());
+        assert_eq!(parity.to_table().to_string(), "1001");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod anf;
+pub mod walsh;
+pub mod post_lattice;
@@ -0,0 +1,245 @@
+use std::fmt;
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{parse_formula, ASTNode, Expr, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::bitset::MAX_TABLE_VARIABLES;
+use crate::ex04::truth_table::{evaluate_truth_table, extract_and_sort_vars_from_ast};
+
+// Post 격자의 다섯 극대 클론에 대한 분류
+// 연결사 집합은 모든 연결사가 함께 만족하는 성질이 하나도 없을 때만 함수적으로 완전 (Post의 정리)
+// 행 번호는 print_truth_table과 같음 (첫 번째 변수가 최상위 비트)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Property {
+    ZeroPreserving, // f(0, ..., 0) = 0
+    OnePreserving,  // f(1, ..., 1) = 1
+    SelfDual,       // f(!x) = !f(x)
+    Monotone,       // x <= y이면 f(x) <= f(y)
+    Affine,         // 변수들의 XOR와 상수로 표현됨
+}
+
+impl Property {
+    pub const ALL: [Property; 5] =
+        [Property::ZeroPreserving, Property::OnePreserving, Property::SelfDual, Property::Monotone, Property::Affine];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Property::ZeroPreserving => "0-preserving",
+            Property::OnePreserving => "1-preserving",
+            Property::SelfDual => "self-dual",
+            Property::Monotone => "monotone",
+            Property::Affine => "affine",
+        }
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// 성질이 깨지는 행. 두 행의 비교로 깨지는 경우(자기쌍대: 보수 행, 단조: 한 변수를 1로 올린 행) related에 상대 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    pub row: Assignment,
+    pub related: Option<Assignment>,
+}
+
+impl fmt::Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.row)?;
+        if let Some(related) = &self.related {
+            write!(f, " vs {}", related)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    vars: Vec<Symbol>,
+    witnesses: [Option<Witness>; 5], // Property::ALL 순서. None이면 성질이 성립
+}
+
+impl Classification {
+    pub fn from_ast(ast: &Expr) -> Result<Classification, FormulaError> {
+        let vars = extract_and_sort_vars_from_ast(ast);
+        let results = evaluate_truth_table(ast, &vars)?;
+        Classification::from_results(&vars, &results)
+    }
+
+    pub fn from_node(node: &ASTNode) -> Result<Classification, FormulaError> {
+        Classification::from_ast(&Expr::try_from(node)?)
+    }
+
+    // results[i]는 i번째 행의 값
+    pub fn from_results(vars: &[Symbol], results: &[bool]) -> Result<Classification, FormulaError> {
+        if vars.len() > MAX_TABLE_VARIABLES {
+            return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_TABLE_VARIABLES });
+        }
+        if results.len() != 1 << vars.len() {
+            return Err(FormulaError::RowCount { expected: 1 << vars.len(), found: results.len() });
+        }
+        let witness = |row: usize, related: Option<usize>| Witness {
+            row: Assignment::from_row(vars, row as u64),
+            related: related.map(|related| Assignment::from_row(vars, related as u64)),
+        };
+        let rows = results.len();
+        let all = rows - 1;
+
+        let zero_preserving = if results[0] { Some(witness(0, None)) } else { None };
+        let one_preserving = if results[all] { None } else { Some(witness(all, None)) };
+        let self_dual = (0..rows).find(|&row| results[row] == results[all ^ row]).map(|row| witness(row, Some(all ^ row)));
+        // 한 변수만 0에서 1로 바꾸었을 때 1에서 0으로 떨어지는 이웃한 두 행을 찾으면 충분
+        let monotone = (0..rows)
+            .filter(|&row| results[row])
+            .find_map(|row| {
+                (0..vars.len())
+                    .map(|i| 1 << (vars.len() - 1 - i))
+                    .filter(|&bit| row & bit == 0 && !results[row | bit])
+                    .map(|bit| witness(row, Some(row | bit)))
+                    .next()
+            });
+        // 아핀 함수라면 0행과 변수 하나만 1인 행들의 값으로 결정됨. 그 예측과 다른 행이 반례
+        let affine = (0..rows)
+            .find(|&row| {
+                let predicted = (0..vars.len())
+                    .map(|i| 1 << i)
+                    .filter(|&bit| row & bit != 0)
+                    .fold(results[0], |acc, bit| acc ^ results[bit] ^ results[0]);
+                predicted != results[row]
+            })
+            .map(|row| witness(row, None));
+
+        Ok(Classification {
+            vars: vars.to_vec(),
+            witnesses: [zero_preserving, one_preserving, self_dual, monotone, affine],
+        })
+    }
+
+    pub fn vars(&self) -> &[Symbol] {
+        &self.vars
+    }
+
+    pub fn holds(&self, property: Property) -> bool {
+        self.witness(property).is_none()
+    }
+
+    pub fn witness(&self, property: Property) -> Option<&Witness> {
+        self.witnesses[property as usize].as_ref()
+    }
+
+    // 성립하는 성질들
+    pub fn properties(&self) -> Vec<Property> {
+        Property::ALL.into_iter().filter(|&property| self.holds(property)).collect()
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for property in Property::ALL {
+            match self.witness(property) {
+                None => writeln!(f, "{}: yes", property)?,
+                Some(witness) if witness.row.is_empty() => writeln!(f, "{}: no", property)?,
+                Some(witness) => writeln!(f, "{}: no ({})", property, witness)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// 모든 연결사가 함께 만족하는 성질. 비어 있으면 함수적으로 완전
+pub fn common_properties(connectives: &[Expr]) -> Result<Vec<Property>, FormulaError> {
+    let classifications = connectives.iter().map(Classification::from_ast).collect::<Result<Vec<_>, _>>()?;
+    Ok(Property::ALL
+        .into_iter()
+        .filter(|&property| classifications.iter().all(|classification| classification.holds(property)))
+        .collect())
+}
+
+pub fn is_functionally_complete(connectives: &[Expr]) -> Result<bool, FormulaError> {
+    Ok(common_properties(connectives)?.is_empty())
+}
+
+/// 주어진 표기법으로 읽은 식을 분류
+pub fn try_classify(formula: &str, notation: Notation) -> Result<Classification, FormulaError> {
+    Classification::from_ast(&parse_formula(formula, notation)?)
+}
+
+/// 각 식을 연결사 하나로 보고 함수적 완전성을 판정. 예: ["AB|!"]는 NOR
+pub fn try_is_functionally_complete(formulas: &[&str], notation: Notation) -> Result<bool, FormulaError> {
+    let connectives = formulas.iter().map(|formula| parse_formula(formula, notation)).collect::<Result<Vec<_>, _>>()?;
+    is_functionally_complete(&connectives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    fn classify(formula: &str) -> Classification {
+        try_classify(formula, Notation::Postfix).unwrap()
+    }
+
+    #[test]
+    fn test_classification() {
+        use Property::*;
+        assert_eq!(classify("AB&").properties(), vec![ZeroPreserving, OnePreserving, Monotone]);
+        assert_eq!(classify("AB|").properties(), vec![ZeroPreserving, OnePreserving, Monotone]);
+        assert_eq!(classify("AB^").properties(), vec![ZeroPreserving, Affine]);
+        assert_eq!(classify("AB=").properties(), vec![OnePreserving, Affine]);
+        assert_eq!(classify("A!").properties(), vec![SelfDual, Affine]);
+        assert_eq!(classify("A").properties(), Property::ALL.to_vec());
+        assert_eq!(classify("AB&!").properties(), vec![]);
+        assert_eq!(classify("AB>").properties(), vec![OnePreserving]);
+        // 다수결 함수와 3변수 패리티는 자기쌍대
+        assert_eq!(classify("AB&AC&|BC&|").properties(), vec![ZeroPreserving, OnePreserving, SelfDual, Monotone]);
+        assert_eq!(classify("AB^C^").properties(), Property::ALL.iter().copied().filter(|&p| p != Monotone).collect::<Vec<_>>());
+        assert_eq!(classify("1").properties(), vec![OnePreserving, Monotone, Affine]);
+
+        // 반례 행
+        let nand = classify("AB&!");
+        let row = |a: bool, b: bool| Assignment::new().with('A', a).with('B', b);
+        assert_eq!(nand.witness(ZeroPreserving), Some(&Witness { row: row(false, false), related: None }));
+        assert_eq!(nand.witness(OnePreserving), Some(&Witness { row: row(true, true), related: None }));
+        assert_eq!(nand.witness(SelfDual), Some(&Witness { row: row(false, true), related: Some(row(true, false)) }));
+        assert_eq!(nand.witness(Monotone), Some(&Witness { row: row(false, true), related: Some(row(true, true)) }));
+        assert_eq!(nand.witness(Affine), Some(&Witness { row: row(true, true), related: None }));
+        assert_eq!(
+            nand.to_string(),
+            "0-preserving: no (A=0 B=0)\n1-preserving: no (A=1 B=1)\nself-dual: no (A=0 B=1 vs A=1 B=0)\n\
+             monotone: no (A=0 B=1 vs A=1 B=1)\naffine: no (A=1 B=1)\n"
+        );
+        assert_eq!(classify("1").to_string(), "0-preserving: no\n1-preserving: yes\nself-dual: no\nmonotone: yes\naffine: yes\n");
+
+        // 기존 ASTNode도 받음
+        let node = ASTNode::Operator('^', Box::new(ASTNode::Operand('A')), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(Classification::from_node(&node).unwrap().properties(), vec![ZeroPreserving, Affine]);
+        let bad = ASTNode::Operator('?', Box::new(ASTNode::Operand('A')), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(Classification::from_node(&bad), Err(FormulaError::UnknownOperator('?')));
+        let vars = [Symbol::from('A'), Symbol::from('B')];
+        assert_eq!(Classification::from_results(&vars, &[false, true, true, false]).unwrap().properties(), vec![ZeroPreserving, Affine]);
+        assert_eq!(Classification::from_results(&vars, &[false, true]), Err(FormulaError::RowCount { expected: 4, found: 2 }));
+    }
+
+    #[test]
+    fn test_functional_completeness() {
+        let complete = |formulas: &[&str]| try_is_functionally_complete(formulas, Notation::Postfix).unwrap();
+        assert!(complete(&["AB&!"]));
+        assert!(complete(&["AB|!"]));
+        assert!(complete(&["AB&", "A!"]));
+        assert!(complete(&["AB>", "0"]));
+        assert!(complete(&["AB&", "AB^", "1"]));
+        assert!(!complete(&["AB&", "AB|"]));
+        assert!(!complete(&["AB>"]));
+        assert!(!complete(&["AB^", "AB="]));
+        assert!(!complete(&["AB&", "AB|", "0", "1"]));
+        assert!(!complete(&[]));
+
+        let connectives = vec![parse_expr("AB&").unwrap(), parse_expr("AB|").unwrap(), parse_expr("1").unwrap()];
+        assert_eq!(common_properties(&connectives).unwrap(), vec![Property::OnePreserving, Property::Monotone]);
+        assert_eq!(try_is_functionally_complete(&["AB&", "A&"], Notation::Postfix), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use crate::ex03::ast::{parse_formula, Expr, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex04::bitset::TruthTable;
+use super::anf::Anf;
+
+// 암호학적 불 함수 분석: Walsh-Hadamard 스펙트럼과 그로부터 얻는 지표
+// W(u) = Σ_x (-1)^(f(x) ^ u·x). u·x는 행 번호의 비트 AND의 패리티 (행 u는 u의 비트가 1인 변수들의 선형 함수)
+// 스펙트럼과 자기상관은 2^n개의 정수를 저장하므로 변수가 많으면 메모리를 많이 사용
+
+// 길이가 2의 거듭제곱인 배열에 대한 제자리 Walsh-Hadamard 변환
+fn fwht<T: Copy + Add<Output = T> + Sub<Output = T>>(values: &mut [T]) {
+    let mut half = 1;
+    while half < values.len() {
+        for start in (0..values.len()).step_by(half * 2) {
+            for i in start..start + half {
+                let (a, b) = (values[i], values[i + half]);
+                values[i] = a + b;
+                values[i + half] = a - b;
+            }
+        }
+        half *= 2;
+    }
+}
+
+// Walsh 스펙트럼. 결과[u]는 f와 선형 함수 u의 일치하는 행 수에서 다른 행 수를 뺀 값
+pub fn walsh_spectrum(table: &TruthTable) -> Vec<i64> {
+    let mut values: Vec<i64> = (0..table.len()).map(|row| if table.get(row) { -1 } else { 1 }).collect();
+    fwht(&mut values);
+    values
+}
+
+// 자기상관 스펙트럼 r(a) = Σ_x (-1)^(f(x) ^ f(x ^ a))
+// Wiener-Khinchin 정리로 W(u)^2의 변환을 2^n으로 나누어 구함
+pub fn autocorrelation_spectrum(table: &TruthTable) -> Vec<i64> {
+    let mut values: Vec<i128> = walsh_spectrum(table).iter().map(|&w| (w as i128) * (w as i128)).collect();
+    fwht(&mut values);
+    let len = table.len() as i128;
+    values.iter().map(|&value| (value / len) as i64).collect()
+}
+
+fn max_abs(values: &[i64]) -> u64 {
+    values.iter().map(|value| value.unsigned_abs()).max().unwrap_or(0)
+}
+
+// 비선형성: 가장 가까운 아핀 함수와의 해밍 거리 = (2^n - max|W(u)|) / 2
+pub fn nonlinearity(table: &TruthTable) -> u64 {
+    (table.len() as u64 - max_abs(&walsh_spectrum(table))) / 2
+}
+
+// 0과 1의 개수가 같은지
+pub fn is_balanced(table: &TruthTable) -> bool {
+    2 * table.count_ones() == table.len() as u64
+}
+
+// 상관 면역 차수: 1 <= wt(u) <= m인 모든 u에서 W(u) = 0인 가장 큰 m
+pub fn correlation_immunity(table: &TruthTable) -> usize {
+    correlation_immunity_of(&walsh_spectrum(table), table.num_vars())
+}
+
+fn correlation_immunity_of(spectrum: &[i64], num_vars: usize) -> usize {
+    spectrum
+        .iter()
+        .enumerate()
+        .filter(|&(u, &w)| u != 0 && w != 0)
+        .map(|(u, _)| u.count_ones() as usize - 1)
+        .min()
+        .unwrap_or(num_vars)
+}
+
+// 탄력성(resiliency) 차수: 균형 함수일 때의 상관 면역 차수. 균형이 아니면 None
+pub fn resiliency(table: &TruthTable) -> Option<usize> {
+    if is_balanced(table) { Some(correlation_immunity(table)) } else { None }
+}
+
+// 굽은(bent) 함수: 변수 개수가 짝수이고 모든 |W(u)| = 2^(n/2). 비선형성이 가능한 최댓값
+pub fn is_bent(table: &TruthTable) -> bool {
+    is_bent_spectrum(&walsh_spectrum(table), table.num_vars())
+}
+
+fn is_bent_spectrum(spectrum: &[i64], num_vars: usize) -> bool {
+    num_vars.is_multiple_of(2) && spectrum.iter().all(|w| w.unsigned_abs() == 1 << (num_vars / 2))
+}
+
+// 대수적 면역도: f * g = 0 또는 (f ^ 1) * g = 0인 0이 아닌 g의 최소 차수
+// 차수 d 이하의 단항식 계수를 미지수로 두고, 지지 집합의 각 점에서 g(x) = 0인 연립방정식이
+// 0이 아닌 해를 갖는지 GF(2) 소거로 확인. 값은 항상 ceil(n / 2) 이하
+pub fn algebraic_immunity(table: &TruthTable) -> usize {
+    let n = table.num_vars();
+    let ones: Vec<usize> = (0..table.len()).filter(|&row| table.get(row)).collect();
+    let zeros: Vec<usize> = (0..table.len()).filter(|&row| !table.get(row)).collect();
+    (0..n.div_ceil(2))
+        .find(|&degree| has_annihilator(n, &ones, degree) || has_annihilator(n, &zeros, degree))
+        .unwrap_or(n.div_ceil(2))
+}
+
+// points 위에서 모두 0이 되는 차수 degree 이하의 0이 아닌 함수가 있는지
+fn has_annihilator(num_vars: usize, points: &[usize], degree: usize) -> bool {
+    let monomials: Vec<usize> = (0..1usize << num_vars).filter(|u| u.count_ones() as usize <= degree).collect();
+    let width = monomials.len().div_ceil(64);
+    // 소거된 행들. pivots[i]는 basis[i]의 첫 번째 1인 열
+    let mut basis: Vec<Vec<u64>> = Vec::new();
+    let mut pivots: Vec<usize> = Vec::new();
+    for &point in points {
+        // 단항식 u는 u의 변수가 모두 1인 점에서만 1
+        let mut row = vec![0u64; width];
+        for (column, &u) in monomials.iter().enumerate() {
+            if u & point == u {
+                row[column / 64] |= 1 << (column % 64);
+            }
+        }
+        for (reduced, &pivot) in basis.iter().zip(&pivots) {
+            if row[pivot / 64] >> (pivot % 64) & 1 == 1 {
+                row.iter_mut().zip(reduced).for_each(|(word, bits)| *word ^= bits);
+            }
+        }
+        if let Some(pivot) = first_bit(&row) {
+            basis.push(row);
+            pivots.push(pivot);
+            if basis.len() == monomials.len() {
+                return false;
+            }
+        }
+    }
+    basis.len() < monomials.len()
+}
+
+fn first_bit(row: &[u64]) -> Option<usize> {
+    row.iter().enumerate().find(|&(_, &word)| word != 0).map(|(index, word)| index * 64 + word.trailing_zeros() as usize)
+}
+
+// 한 함수에 대한 지표 모음
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoProfile {
+    pub num_vars: usize,
+    pub weight: u64,
+    pub balanced: bool,
+    pub algebraic_degree: usize,
+    pub nonlinearity: u64,
+    pub correlation_immunity: usize,
+    pub resiliency: Option<usize>,
+    pub algebraic_immunity: usize,
+    pub absolute_indicator: u64, // a != 0에서 |r(a)|의 최댓값
+    pub bent: bool,
+}
+
+impl CryptoProfile {
+    pub fn from_ast(ast: &Expr) -> Result<CryptoProfile, FormulaError> {
+        Ok(CryptoProfile::from_table(&TruthTable::from_ast(ast)?))
+    }
+
+    pub fn from_table(table: &TruthTable) -> CryptoProfile {
+        let n = table.num_vars();
+        let spectrum = walsh_spectrum(table);
+        let balanced = is_balanced(table);
+        let correlation_immunity = correlation_immunity_of(&spectrum, n);
+        CryptoProfile {
+            num_vars: n,
+            weight: table.count_ones(),
+            balanced,
+            algebraic_degree: Anf::from_table(table).degree(),
+            nonlinearity: (table.len() as u64 - max_abs(&spectrum)) / 2,
+            correlation_immunity,
+            resiliency: if balanced { Some(correlation_immunity) } else { None },
+            algebraic_immunity: algebraic_immunity(table),
+            absolute_indicator: max_abs(&autocorrelation_spectrum(table)[1..]),
+            bent: is_bent_spectrum(&spectrum, n),
+        }
+    }
+}
+
+impl fmt::Display for CryptoProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resiliency = self.resiliency.map_or_else(|| "-".to_string(), |order| order.to_string());
+        writeln!(f, "variables: {}", self.num_vars)?;
+        writeln!(f, "weight: {}", self.weight)?;
+        writeln!(f, "balanced: {}", self.balanced)?;
+        writeln!(f, "algebraic degree: {}", self.algebraic_degree)?;
+        writeln!(f, "nonlinearity: {}", self.nonlinearity)?;
+        writeln!(f, "correlation immunity: {}", self.correlation_immunity)?;
+        writeln!(f, "resiliency: {}", resiliency)?;
+        writeln!(f, "algebraic immunity: {}", self.algebraic_immunity)?;
+        writeln!(f, "absolute indicator: {}", self.absolute_indicator)?;
+        writeln!(f, "bent: {}", self.bent)
+    }
+}
+
+/// 주어진 표기법으로 읽은 식의 암호학적 지표
+pub fn try_crypto_profile(formula: &str, notation: Notation) -> Result<CryptoProfile, FormulaError> {
+    CryptoProfile::from_ast(&parse_formula(formula, notation)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex03::symbol::Symbol;
+
+    fn table(formula: &str) -> TruthTable {
+        TruthTable::from_ast(&parse_expr(formula).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_spectra() {
+        // A & B: 상수 0, B, A와는 3행 일치하고 A ^ B와는 1행만 일치
+        assert_eq!(walsh_spectrum(&table("AB&")), vec![2, 2, 2, -2]);
+        // 선형 함수의 스펙트럼은 한 곳에만 집중
+        let vars: Vec<Symbol> = "ABC".chars().map(Symbol::from).collect();
+        let linear = TruthTable::from_ast_with_vars(&parse_expr("AC^").unwrap(), &vars).unwrap();
+        assert_eq!(walsh_spectrum(&linear), vec![0, 0, 0, 0, 0, 8, 0, 0]);
+        assert_eq!(autocorrelation_spectrum(&table("A")), vec![2, -2]);
+        assert_eq!(autocorrelation_spectrum(&table("AB&")), vec![4, 0, 0, 0]);
+
+        // 파시발 항등식: Σ W(u)^2 = 2^(2n)
+        let f = table("AB&C^DE|&F>");
+        let spectrum = walsh_spectrum(&f);
+        assert_eq!(spectrum.iter().map(|w| w * w).sum::<i64>(), 1 << 12);
+        // 자기상관을 정의대로 계산한 값과 비교
+        let direct: Vec<i64> = (0..f.len())
+            .map(|a| (0..f.len()).map(|x| if f.get(x) == f.get(x ^ a) { 1 } else { -1 }).sum())
+            .collect();
+        assert_eq!(autocorrelation_spectrum(&f), direct);
+
+        // 단어 경계를 넘는 8개 변수: 굽은 함수의 자기상관은 0이 아닌 곳에서 모두 0
+        let bent = table("AB&CD&^EF&^GH&^");
+        assert!(is_bent(&bent));
+        assert!(autocorrelation_spectrum(&bent)[1..].iter().all(|&r| r == 0));
+        assert_eq!(nonlinearity(&bent), 120);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let bent = table("AB&CD&^");
+        assert!(is_bent(&bent));
+        assert_eq!(nonlinearity(&bent), 6);
+        assert!(!is_balanced(&bent));
+        assert_eq!(resiliency(&bent), None);
+        assert!(!is_bent(&table("AB&C^")));
+        assert!(!is_bent(&table("AB^")));
+
+        // 패리티: 선형이고 2차 상관 면역
+        let parity = table("AB^C^");
+        assert!(is_balanced(&parity));
+        assert_eq!(nonlinearity(&parity), 0);
+        assert_eq!(correlation_immunity(&parity), 2);
+        assert_eq!(resiliency(&parity), Some(2));
+
+        // 다수결 함수는 균형이지만 각 변수와 상관됨
+        let majority = table("AB&AC&|BC&|");
+        assert_eq!(resiliency(&majority), Some(0));
+        assert_eq!(nonlinearity(&majority), 2);
+        assert_eq!(algebraic_immunity(&majority), 2);
+
+        assert_eq!(algebraic_immunity(&table("AB&")), 1);
+        assert_eq!(algebraic_immunity(&table("A")), 1);
+        assert_eq!(algebraic_immunity(&table("AA!&")), 0);
+        assert_eq!(correlation_immunity(&table("AA!&")), 1);
+        // 6개 변수에서 대수적 면역도의 최댓값 3에 도달하는 다수결 함수
+        let vars: Vec<Symbol> = "ABCDEF".chars().map(Symbol::from).collect();
+        let majority6: Vec<bool> = (0..64u32).map(|row| row.count_ones() >= 3).collect();
+        assert_eq!(algebraic_immunity(&TruthTable::from_bools(&vars, &majority6).unwrap()), 3);
+    }
+
+    #[test]
+    fn test_profile() {
+        let profile = try_crypto_profile("A & B ^ C & D", Notation::Infix).unwrap();
+        assert_eq!(
+            profile.to_string(),
+            "variables: 4\nweight: 6\nbalanced: false\nalgebraic degree: 2\nnonlinearity: 6\n\
+             correlation immunity: 0\nresiliency: -\nalgebraic immunity: 2\nabsolute indicator: 0\nbent: true\n"
+        );
+        let profile = try_crypto_profile("AB^C^", Notation::Postfix).unwrap();
+        assert_eq!(profile.resiliency, Some(2));
+        assert_eq!(profile.absolute_indicator, 8);
+        assert_eq!(profile.algebraic_degree, 1);
+        assert_eq!(try_crypto_profile("A&", Notation::Postfix), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+    }
+}
@@ -0,0 +1 @@
+pub mod robdd;
\ No newline at end of file
@@ -0,0 +1,589 @@
+use std::collections::HashMap;
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{Expr, Op};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+
+// 축약된 순서 이진 결정 다이어그램 (ROBDD)
+// - unique table로 같은 (변수, low, high) 노드를 하나만 만들어 정규형을 보장
+// - computed cache로 apply 결과를 재사용
+// 같은 매니저 안에서 동치인 식은 항상 같은 Bdd 값이 된다
+
+// sat_count 결과가 u128에 들어가는 최대 변수 수
+pub const MAX_SAT_COUNT_VARIABLES: usize = 127;
+
+// Bdd 핸들(u32)로 가리킬 수 있는 가장 큰 노드 번호
+pub const MAX_NODE_INDEX: usize = u32::MAX as usize;
+
+// 매니저 안의 노드를 가리키는 핸들. 0과 1은 상수 노드
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bdd(u32);
+
+impl Bdd {
+    pub const FALSE: Bdd = Bdd(0);
+    pub const TRUE: Bdd = Bdd(1);
+
+    pub fn constant(value: bool) -> Bdd {
+        if value { Bdd::TRUE } else { Bdd::FALSE }
+    }
+
+    pub fn is_constant(self) -> bool {
+        self.0 < 2
+    }
+
+    // nodes의 index번째 노드를 가리키는 핸들. u32에 들어가지 않으면 에러
+    fn from_index(index: usize) -> Result<Bdd, FormulaError> {
+        u32::try_from(index).map(Bdd).map_err(|_| FormulaError::TooManyNodes { limit: MAX_NODE_INDEX })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    var: usize, // 변수 번호 (variables의 인덱스). 상수 노드는 usize::MAX
+    low: Bdd,
+    high: Bdd,
+}
+
+pub struct BddManager {
+    nodes: Vec<Node>,
+    unique: HashMap<(usize, Bdd, Bdd), Bdd>,
+    cache: HashMap<(Op, Bdd, Bdd), Bdd>,
+    variables: Vec<Symbol>,
+    var_index: HashMap<Symbol, usize>,
+    levels: Vec<usize>, // 변수 번호 -> 순서상의 위치 (0이 루트 쪽)
+    order: Vec<usize>,  // 순서상의 위치 -> 변수 번호 (levels의 역)
+}
+
+impl Default for BddManager {
+    fn default() -> Self {
+        BddManager::new()
+    }
+}
+
+impl BddManager {
+    pub fn new() -> BddManager {
+        let terminal = |_| Node { var: usize::MAX, low: Bdd::FALSE, high: Bdd::FALSE };
+        BddManager {
+            nodes: (0..2).map(terminal).collect(),
+            unique: HashMap::new(),
+            cache: HashMap::new(),
+            variables: Vec::new(),
+            var_index: HashMap::new(),
+            levels: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    // 주어진 변수 순서로 시작하는 매니저
+    pub fn with_order(order: &[Symbol]) -> Result<BddManager, FormulaError> {
+        let mut manager = BddManager::new();
+        for &var in order {
+            manager.var(var)?;
+        }
+        Ok(manager)
+    }
+
+    // 현재 변수 순서 (루트 쪽부터)
+    pub fn order(&self) -> Vec<Symbol> {
+        self.order.iter().map(|&index| self.variables[index]).collect()
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.variables.len()
+    }
+
+    // 지금까지 만든 노드 수 (상수 노드 포함)
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // 변수 하나로 이루어진 BDD. 처음 보는 변수는 순서의 맨 아래에 추가
+    // 이하 노드를 만드는 연산은 노드 번호가 u32를 넘으면 TooManyNodes 에러
+    pub fn var(&mut self, var: Symbol) -> Result<Bdd, FormulaError> {
+        let index = match self.var_index.get(&var) {
+            Some(&index) => index,
+            None => {
+                let index = self.variables.len();
+                self.variables.push(var);
+                self.var_index.insert(var, index);
+                self.levels.push(index);
+                self.order.push(index);
+                index
+            }
+        };
+        self.make_node(index, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    fn make_node(&mut self, var: usize, low: Bdd, high: Bdd) -> Result<Bdd, FormulaError> {
+        if low == high {
+            return Ok(low);
+        }
+        if let Some(&node) = self.unique.get(&(var, low, high)) {
+            return Ok(node);
+        }
+        let node = Bdd::from_index(self.nodes.len())?;
+        self.nodes.push(Node { var, low, high });
+        self.unique.insert((var, low, high), node);
+        Ok(node)
+    }
+
+    fn level(&self, f: Bdd) -> usize {
+        if f.is_constant() { self.variables.len() } else { self.levels[self.nodes[f.0 as usize].var] }
+    }
+
+    // 노드의 변수에 대한 (low, high) 자식. 그 변수에 의존하지 않는 노드는 자기 자신
+    fn cofactors(&self, f: Bdd, level: usize) -> (Bdd, Bdd) {
+        if self.level(f) == level {
+            let node = self.nodes[f.0 as usize];
+            (node.low, node.high)
+        } else {
+            (f, f)
+        }
+    }
+
+    fn var_at_level(&self, level: usize) -> usize {
+        self.order[level]
+    }
+
+    // AST를 BDD로 변환. 새 변수는 진리표 열 순서대로 추가
+    pub fn from_ast(&mut self, ast: &Expr) -> Result<Bdd, FormulaError> {
+        for var in extract_and_sort_vars_from_ast(ast) {
+            self.var(var)?;
+        }
+        self.build(ast)
+    }
+
+    fn build(&mut self, ast: &Expr) -> Result<Bdd, FormulaError> {
+        match ast {
+            Expr::Const(value) => Ok(Bdd::constant(*value)),
+            Expr::Var(var) => self.var(*var),
+            Expr::Not(operand) => {
+                let operand = self.build(operand)?;
+                self.not(operand)
+            }
+            Expr::Binary(op, left, right) => {
+                let left = self.build(left)?;
+                let right = self.build(right)?;
+                self.apply(*op, left, right)
+            }
+        }
+    }
+
+    pub fn not(&mut self, f: Bdd) -> Result<Bdd, FormulaError> {
+        self.apply(Op::Xor, f, Bdd::TRUE)
+    }
+
+    // calculate가 지원하는 모든 이항 연산자를 BDD에 적용
+    pub fn apply(&mut self, op: Op, f: Bdd, g: Bdd) -> Result<Bdd, FormulaError> {
+        if f.is_constant() && g.is_constant() {
+            return Ok(Bdd::constant(op.apply(f == Bdd::TRUE, g == Bdd::TRUE)));
+        }
+        if let Some(result) = Self::shortcut(op, f, g) {
+            return Ok(result);
+        }
+        if let Some(&result) = self.cache.get(&(op, f, g)) {
+            return Ok(result);
+        }
+
+        let level = self.level(f).min(self.level(g));
+        let (f_low, f_high) = self.cofactors(f, level);
+        let (g_low, g_high) = self.cofactors(g, level);
+        let low = self.apply(op, f_low, g_low)?;
+        let high = self.apply(op, f_high, g_high)?;
+        let result = self.make_node(self.var_at_level(level), low, high)?;
+        self.cache.insert((op, f, g), result);
+        Ok(result)
+    }
+
+    // 한쪽이 상수이거나 두 피연산자가 같을 때 바로 결과를 알 수 있는 경우
+    fn shortcut(op: Op, f: Bdd, g: Bdd) -> Option<Bdd> {
+        match op {
+            Op::And if f == Bdd::FALSE || g == Bdd::FALSE => Some(Bdd::FALSE),
+            Op::And if f == Bdd::TRUE || f == g => Some(g),
+            Op::And if g == Bdd::TRUE => Some(f),
+            Op::Or if f == Bdd::TRUE || g == Bdd::TRUE => Some(Bdd::TRUE),
+            Op::Or if f == Bdd::FALSE || f == g => Some(g),
+            Op::Or if g == Bdd::FALSE => Some(f),
+            Op::Xor if f == g => Some(Bdd::FALSE),
+            Op::Xor if f == Bdd::FALSE => Some(g),
+            Op::Xor if g == Bdd::FALSE => Some(f),
+            Op::Iff if f == g => Some(Bdd::TRUE),
+            Op::Iff if f == Bdd::TRUE => Some(g),
+            Op::Iff if g == Bdd::TRUE => Some(f),
+            Op::Implies if f == Bdd::FALSE || g == Bdd::TRUE || f == g => Some(Bdd::TRUE),
+            Op::Implies if f == Bdd::TRUE => Some(g),
+            _ => None,
+        }
+    }
+
+    // if f then g else h
+    pub fn ite(&mut self, f: Bdd, g: Bdd, h: Bdd) -> Result<Bdd, FormulaError> {
+        let then_part = self.apply(Op::And, f, g)?;
+        let not_f = self.not(f)?;
+        let else_part = self.apply(Op::And, not_f, h)?;
+        self.apply(Op::Or, then_part, else_part)
+    }
+
+    // 변수에 값을 대입한 BDD (f|var=value)
+    pub fn restrict(&mut self, f: Bdd, var: Symbol, value: bool) -> Result<Bdd, FormulaError> {
+        let level = match self.var_index.get(&var) {
+            Some(&index) => self.levels[index],
+            None => return Ok(f),
+        };
+        let mut memo = HashMap::new();
+        self.restrict_at(f, level, value, &mut memo)
+    }
+
+    fn restrict_at(
+        &mut self,
+        f: Bdd,
+        level: usize,
+        value: bool,
+        memo: &mut HashMap<Bdd, Bdd>,
+    ) -> Result<Bdd, FormulaError> {
+        let f_level = self.level(f);
+        if f_level > level {
+            return Ok(f);
+        }
+        let node = self.nodes[f.0 as usize];
+        if f_level == level {
+            return Ok(if value { node.high } else { node.low });
+        }
+        if let Some(&result) = memo.get(&f) {
+            return Ok(result);
+        }
+        let low = self.restrict_at(node.low, level, value, memo)?;
+        let high = self.restrict_at(node.high, level, value, memo)?;
+        let result = self.make_node(node.var, low, high)?;
+        memo.insert(f, result);
+        Ok(result)
+    }
+
+    // 변수 자리에 다른 BDD를 대입: f[var := g]
+    pub fn compose(&mut self, f: Bdd, var: Symbol, g: Bdd) -> Result<Bdd, FormulaError> {
+        let high = self.restrict(f, var, true)?;
+        let low = self.restrict(f, var, false)?;
+        self.ite(g, high, low)
+    }
+
+    // 존재 한정: f|var=0 | f|var=1
+    pub fn exists(&mut self, f: Bdd, var: Symbol) -> Result<Bdd, FormulaError> {
+        let low = self.restrict(f, var, false)?;
+        let high = self.restrict(f, var, true)?;
+        self.apply(Op::Or, low, high)
+    }
+
+    // 전칭 한정: f|var=0 & f|var=1
+    pub fn forall(&mut self, f: Bdd, var: Symbol) -> Result<Bdd, FormulaError> {
+        let low = self.restrict(f, var, false)?;
+        let high = self.restrict(f, var, true)?;
+        self.apply(Op::And, low, high)
+    }
+
+    // 매니저에 등록된 모든 변수에 대한 만족 할당의 수. 변수가 127개를 넘으면 u128에 들어가지 않으므로 에러
+    pub fn sat_count(&self, f: Bdd) -> Result<u128, FormulaError> {
+        if self.variables.len() > MAX_SAT_COUNT_VARIABLES {
+            return Err(FormulaError::TooManyVariables { count: self.variables.len(), limit: MAX_SAT_COUNT_VARIABLES });
+        }
+        let mut memo = HashMap::new();
+        Ok(self.count_from(f, &mut memo) << self.level(f))
+    }
+
+    // f의 위치부터 아래쪽 변수들에 대한 만족 할당의 수
+    fn count_from(&self, f: Bdd, memo: &mut HashMap<Bdd, u128>) -> u128 {
+        if f.is_constant() {
+            return (f == Bdd::TRUE) as u128;
+        }
+        if let Some(&count) = memo.get(&f) {
+            return count;
+        }
+        let node = self.nodes[f.0 as usize];
+        let level = self.level(f);
+        let low = self.count_from(node.low, memo) << (self.level(node.low) - level - 1);
+        let high = self.count_from(node.high, memo) << (self.level(node.high) - level - 1);
+        memo.insert(f, low + high);
+        low + high
+    }
+
+    // 만족하는 할당 하나. BDD 경로에 나오지 않는 변수는 0으로 둠
+    pub fn any_sat(&self, f: Bdd) -> Option<Assignment> {
+        if f == Bdd::FALSE {
+            return None;
+        }
+        let mut assignment: Assignment = self.variables.iter().map(|&var| (var, false)).collect();
+        let mut current = f;
+        while !current.is_constant() {
+            let node = self.nodes[current.0 as usize];
+            // FALSE가 아닌 쪽으로 내려감 (ROBDD에서 FALSE가 아닌 노드는 항상 만족 가능)
+            if node.low != Bdd::FALSE {
+                current = node.low;
+            } else {
+                assignment.set(self.variables[node.var], true);
+                current = node.high;
+            }
+        }
+        Some(assignment)
+    }
+
+    // 할당으로 BDD를 평가
+    pub fn eval(&self, f: Bdd, assignment: &Assignment) -> Option<bool> {
+        let mut current = f;
+        while !current.is_constant() {
+            let node = self.nodes[current.0 as usize];
+            current = if assignment.get(self.variables[node.var])? { node.high } else { node.low };
+        }
+        Some(current == Bdd::TRUE)
+    }
+
+    // roots에서 도달할 수 있는 노드 수 (상수 노드 포함)
+    pub fn node_count(&self, roots: &[Bdd]) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack: Vec<Bdd> = roots.to_vec();
+        while let Some(f) = stack.pop() {
+            if !seen.insert(f) || f.is_constant() {
+                continue;
+            }
+            let node = self.nodes[f.0 as usize];
+            stack.push(node.low);
+            stack.push(node.high);
+        }
+        seen.len()
+    }
+
+    // 새 변수 순서로 roots를 다시 만든다. 다른 노드는 모두 버려지므로 반환된 새 핸들을 사용해야 함
+    // 에러가 나면 매니저는 바뀌지 않음
+    pub fn reorder(&mut self, roots: &[Bdd], order: &[Symbol]) -> Result<Vec<Bdd>, FormulaError> {
+        let mut target = BddManager::with_order(order)?;
+        for &var in &self.variables {
+            target.var(var)?;
+        }
+        let mut memo = HashMap::new();
+        let new_roots = roots
+            .iter()
+            .map(|&root| self.transfer(root, &mut target, &mut memo))
+            .collect::<Result<Vec<Bdd>, FormulaError>>()?;
+        *self = target;
+        Ok(new_roots)
+    }
+
+    fn transfer(
+        &self,
+        f: Bdd,
+        target: &mut BddManager,
+        memo: &mut HashMap<Bdd, Bdd>,
+    ) -> Result<Bdd, FormulaError> {
+        if f.is_constant() {
+            return Ok(f);
+        }
+        if let Some(&result) = memo.get(&f) {
+            return Ok(result);
+        }
+        let node = self.nodes[f.0 as usize];
+        let low = self.transfer(node.low, target, memo)?;
+        let high = self.transfer(node.high, target, memo)?;
+        let var = target.var(self.variables[node.var])?;
+        let result = target.ite(var, high, low)?;
+        memo.insert(f, result);
+        Ok(result)
+    }
+
+    // 이웃한 두 위치 level, level + 1의 변수를 맞바꿈. 노드를 제자리에서 고쳐 쓰므로 모든 핸들이 같은 함수를 유지함
+    // 위쪽 변수 x의 노드 중 아래쪽 변수 y에 의존하는 것만 y 노드로 바뀌고, 쓰이지 않게 된 노드는 nodes에 남음
+    // 도중에 TooManyNodes 에러가 나면 매니저가 일관되지 않으므로 버려야 함
+    fn swap_levels(&mut self, level: usize) -> Result<(), FormulaError> {
+        let (x, y) = (self.order[level], self.order[level + 1]);
+        let mut rewrites = Vec::new();
+        for index in 2..self.nodes.len() {
+            let node = self.nodes[index];
+            if node.var != x || (self.level(node.low) != level + 1 && self.level(node.high) != level + 1) {
+                continue;
+            }
+            let (f00, f01) = self.cofactors(node.low, level + 1);
+            let (f10, f11) = self.cofactors(node.high, level + 1);
+            self.unique.remove(&(x, node.low, node.high));
+            rewrites.push((index, f00, f01, f10, f11));
+        }
+        self.levels.swap(x, y);
+        self.order.swap(level, level + 1);
+        for (index, f00, f01, f10, f11) in rewrites {
+            let low = self.make_node(x, f00, f10)?;
+            let high = self.make_node(x, f01, f11)?;
+            self.nodes[index] = Node { var: y, low, high };
+            self.unique.insert((y, low, high), Bdd::from_index(index)?);
+        }
+        Ok(())
+    }
+
+    // 변수 재배치(sifting): 변수를 하나씩 맨 아래, 맨 위로 옮겨 보며 노드 수가 가장 적은 위치에 고정
+    // 이웃한 위치끼리 맞바꾸며 옮기고, 변수 하나를 고정할 때마다 reorder로 쓰이지 않는 노드를 정리함
+    // 따라서 roots 밖의 핸들은 버려지며 반환된 새 핸들을 사용해야 함
+    pub fn sift(&mut self, roots: &[Bdd]) -> Result<Vec<Bdd>, FormulaError> {
+        let mut roots = self.reorder(roots, &self.order())?;
+        let mut best_size = self.node_count(&roots);
+        for var in self.order() {
+            let mut level = self.levels[self.var_index[&var]];
+            let mut best_level = level;
+            let mut measure = |manager: &BddManager, level: usize| {
+                let size = manager.node_count(&roots);
+                if size < best_size {
+                    best_size = size;
+                    best_level = level;
+                }
+            };
+            while level + 1 < self.variables.len() {
+                self.swap_levels(level)?;
+                level += 1;
+                measure(self, level);
+            }
+            while level > 0 {
+                self.swap_levels(level - 1)?;
+                level -= 1;
+                measure(self, level);
+            }
+            while level < best_level {
+                self.swap_levels(level)?;
+                level += 1;
+            }
+            roots = self.reorder(&roots, &self.order())?;
+        }
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    fn build(manager: &mut BddManager, formula: &str) -> Bdd {
+        manager.from_ast(&parse_expr(formula).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_canonical() {
+        let mut manager = BddManager::new();
+        assert_eq!(build(&mut manager, "AB>"), build(&mut manager, "A!B|"));
+        assert_eq!(build(&mut manager, "AB="), build(&mut manager, "AB^!"));
+        assert_eq!(build(&mut manager, "AB&!"), build(&mut manager, "A!B!|"));
+        assert_eq!(build(&mut manager, "AA!|"), Bdd::TRUE);
+        assert_eq!(build(&mut manager, "AA^"), Bdd::FALSE);
+        assert_eq!(build(&mut manager, "A1&"), build(&mut manager, "A"));
+        assert_ne!(build(&mut manager, "AB&"), build(&mut manager, "AB|"));
+
+        // 모든 연산자가 calculate와 같은 결과
+        for op in Op::ALL {
+            let formula = format!("AB{}", op.symbol());
+            let f = build(&mut manager, &formula);
+            for row in 0..4 {
+                let assignment = Assignment::from_row(&[Symbol::from('A'), Symbol::from('B')], row);
+                let expected = op.apply(row & 2 != 0, row & 1 != 0);
+                assert_eq!(manager.eval(f, &assignment), Some(expected), "{}", formula);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let mut manager = BddManager::new();
+        let f = build(&mut manager, "AB&C|");
+        let a = Symbol::from('A');
+        let c = Symbol::from('C');
+        assert_eq!(manager.restrict(f, a, true).unwrap(), build(&mut manager, "BC|"));
+        assert_eq!(manager.restrict(f, a, false).unwrap(), build(&mut manager, "C"));
+        assert_eq!(manager.exists(f, a).unwrap(), build(&mut manager, "BC|"));
+        assert_eq!(manager.forall(f, a).unwrap(), build(&mut manager, "C"));
+        assert_eq!(manager.forall(f, c).unwrap(), build(&mut manager, "AB&"));
+
+        // C := A ^ B
+        let g = build(&mut manager, "AB^");
+        assert_eq!(manager.compose(f, c, g).unwrap(), build(&mut manager, "AB|"));
+
+        let unrelated = Symbol::from('Z');
+        assert_eq!(manager.restrict(f, unrelated, true), Ok(f));
+    }
+
+    #[test]
+    fn test_sat_count() {
+        let mut manager = BddManager::new();
+        let f = build(&mut manager, "AB|");
+        assert_eq!(manager.sat_count(f), Ok(3));
+        let f = build(&mut manager, "AB|C&");
+        assert_eq!(manager.sat_count(f), Ok(3));
+        let c = build(&mut manager, "C");
+        assert_eq!(manager.sat_count(c), Ok(4));
+        assert_eq!(manager.sat_count(Bdd::TRUE), Ok(8));
+        assert_eq!(manager.sat_count(Bdd::FALSE), Ok(0));
+
+        let model = manager.any_sat(f).unwrap();
+        assert_eq!(manager.eval(f, &model), Some(true));
+        assert_eq!(manager.any_sat(Bdd::FALSE), None);
+
+        // 진리표로는 불가능한 크기: 100개 변수의 XOR
+        let mut manager = BddManager::new();
+        let mut f = Bdd::FALSE;
+        for i in 0..100 {
+            let var = manager.var(Symbol::intern(&format!("x{}", i))).unwrap();
+            f = manager.apply(Op::Xor, f, var).unwrap();
+        }
+        assert_eq!(manager.sat_count(f), Ok(1u128 << 99));
+        assert_eq!(manager.node_count(&[f]), 2 * 100 - 1 + 2);
+        for i in 100..128 {
+            manager.var(Symbol::intern(&format!("x{}", i))).unwrap();
+        }
+        assert_eq!(manager.sat_count(f), Err(FormulaError::TooManyVariables { count: 128, limit: 127 }));
+    }
+
+    #[test]
+    fn test_node_index_limit() {
+        // 노드 번호가 u32를 넘으면 핸들이 조용히 겹치지 않고 에러
+        assert_eq!(Bdd::from_index(MAX_NODE_INDEX), Ok(Bdd(u32::MAX)));
+        if let Some(index) = MAX_NODE_INDEX.checked_add(1) {
+            assert_eq!(Bdd::from_index(index), Err(FormulaError::TooManyNodes { limit: MAX_NODE_INDEX }));
+        }
+        assert_eq!(FormulaError::TooManyNodes { limit: 7 }.to_string(), "BDD node index exceeds 7");
+    }
+
+    #[test]
+    fn test_swap_levels() {
+        let mut manager = BddManager::new();
+        let formulas = ["AB&C|", "AB^C^D&", "AC>BD=|", "AB&!CD|&"];
+        let roots: Vec<Bdd> = formulas.iter().map(|formula| build(&mut manager, formula)).collect();
+        let vars: Vec<Symbol> = "ABCD".chars().map(Symbol::from).collect();
+        let tables = |manager: &BddManager| -> Vec<Vec<Option<bool>>> {
+            roots.iter().map(|&f| (0..16).map(|row| manager.eval(f, &Assignment::from_row(&vars, row))).collect()).collect()
+        };
+        let expected = tables(&manager);
+        for level in [0, 2, 1, 0, 2] {
+            manager.swap_levels(level).unwrap();
+            // 핸들은 그대로 같은 함수이며, 새 순서에서도 정규형
+            assert_eq!(tables(&manager), expected);
+            for (&root, formula) in roots.iter().zip(formulas) {
+                assert_eq!(build(&mut manager, formula), root, "{}", formula);
+            }
+        }
+        assert_eq!(manager.order(), "DBCA".chars().map(Symbol::from).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_sift() {
+        // a1&b1 | a2&b2 | a3&b3 | a4&b4: a들을 먼저 두면 지수 크기, 짝을 붙이면 선형 크기
+        let a: Vec<Symbol> = (1..=4).map(|i| Symbol::intern(&format!("a{}", i))).collect();
+        let b: Vec<Symbol> = (1..=4).map(|i| Symbol::intern(&format!("b{}", i))).collect();
+        let order: Vec<Symbol> = a.iter().chain(&b).copied().collect();
+        let mut manager = BddManager::with_order(&order).unwrap();
+        let f = build(&mut manager, "[a1][b1]&[a2][b2]&|[a3][b3]&|[a4][b4]&|");
+        let count = manager.sat_count(f).unwrap();
+        assert_eq!(manager.node_count(&[f]), 32);
+
+        let roots = manager.sift(&[f]).unwrap();
+        assert_eq!(manager.node_count(&roots), 10);
+        assert_eq!(manager.sat_count(roots[0]), Ok(count));
+        assert_eq!(roots[0], build(&mut manager, "[a1][b1]&[a2][b2]&|[a3][b3]&|[a4][b4]&|"));
+        let order = manager.order();
+        for (x, y) in a.iter().zip(&b) {
+            let position = |var: &Symbol| order.iter().position(|v| v == var).unwrap();
+            assert_eq!(position(x).abs_diff(position(y)), 1);
+        }
+    }
+}
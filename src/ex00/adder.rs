@@ -9,6 +9,7 @@ pub fn adder(a: u32, b: u32) -> u32 {
 }
 
 #[cfg(test)]
+#[allow(clippy::legacy_numeric_constants)]
 mod test {
     use super::*;
 
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use super::symbol::Symbol;
+
+// Truth values for a set of variables. Variables are kept sorted by name,
+// which is also the column order of print_truth_table.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Assignment {
+    values: BTreeMap<Symbol, bool>,
+}
+
+impl Assignment {
+    pub fn new() -> Assignment {
+        Assignment::default()
+    }
+
+    // Assigns the variables of one truth table row; the first variable is the most significant bit
+    pub fn from_row(vars: &[Symbol], row: u64) -> Assignment {
+        let num_vars = vars.len();
+        vars.iter()
+            .enumerate()
+            .map(|(j, &var)| (var, (row >> (num_vars - j - 1)) & 1 == 1))
+            .collect()
+    }
+
+    pub fn set(&mut self, var: impl Into<Symbol>, value: bool) {
+        self.values.insert(var.into(), value);
+    }
+
+    pub fn with(mut self, var: impl Into<Symbol>, value: bool) -> Assignment {
+        self.set(var, value);
+        self
+    }
+
+    pub fn get(&self, var: impl Into<Symbol>) -> Option<bool> {
+        self.values.get(&var.into()).copied()
+    }
+
+    pub fn remove(&mut self, var: impl Into<Symbol>) -> Option<bool> {
+        self.values.remove(&var.into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.values.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, bool)> + '_ {
+        self.values.iter().map(|(&var, &value)| (var, value))
+    }
+}
+
+impl FromIterator<(Symbol, bool)> for Assignment {
+    fn from_iter<I: IntoIterator<Item = (Symbol, bool)>>(iter: I) -> Assignment {
+        Assignment { values: iter.into_iter().collect() }
+    }
+}
+
+impl Extend<(Symbol, bool)> for Assignment {
+    fn extend<I: IntoIterator<Item = (Symbol, bool)>>(&mut self, iter: I) {
+        self.values.extend(iter);
+    }
+}
+
+// "A=1 B=0 x17=1"
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.iter().map(|(var, value)| format!("{}={}", var, value as u8)).collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assignment() {
+        let vars = [Symbol::from('A'), Symbol::from('B'), Symbol::from('C')];
+        let assignment = Assignment::from_row(&vars, 0b101);
+        assert_eq!(assignment.get('A'), Some(true));
+        assert_eq!(assignment.get('B'), Some(false));
+        assert_eq!(assignment.get('C'), Some(true));
+        assert_eq!(assignment.get('D'), None);
+        assert_eq!(assignment.to_string(), "A=1 B=0 C=1");
+
+        let assignment = Assignment::new().with("x2", true).with('A', false);
+        assert_eq!(assignment.len(), 2);
+        assert_eq!(assignment.to_string(), "A=0 x2=1");
+    }
+}
@@ -1,98 +1,378 @@
-use anyhow::{Result, anyhow};
 use std::fmt;
+use super::error::FormulaError;
+use super::symbol::Symbol;
 
 #[derive(Debug, PartialEq, Clone)]
 enum Token {
-    Operand(char),  // 피연산자는 문자로 표현 (e.g., 'A', 'B', '0', '1')
+    Const(bool),    // '0', '1'
+    Var(Symbol),    // 'A', "[x17]", 그리고 중위 표기식에서는 "door_open"
     Operator(char), // 연산자는 문자로 표현 (e.g., '&', '|', '!')
+    LeftParen,      // 중위 표기식에서만 사용
+    RightParen,
 }
 
+// Selects how a formula string is written
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Notation {
+    #[default]
+    Postfix, // Reverse Polish, e.g. "AB&C|" or "[x1][door_open]&"
+    Infix,   // e.g. "A & B | C", "A ∧ B ∨ C" or "x1 & door_open"
+}
+
+// Binary connectives. Negation is unary and lives on Expr::Not,
+// so a binary node can never be built with a missing operand.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Op {
+    And,     // '&'
+    Or,      // '|'
+    Xor,     // '^'
+    Implies, // '>'
+    Iff,     // '='
+}
+
+impl Op {
+    pub const ALL: [Op; 5] = [Op::And, Op::Or, Op::Xor, Op::Implies, Op::Iff];
+
+    pub fn from_symbol(symbol: char) -> Option<Op> {
+        match symbol {
+            '&' => Some(Op::And),
+            '|' => Some(Op::Or),
+            '^' => Some(Op::Xor),
+            '>' => Some(Op::Implies),
+            '=' => Some(Op::Iff),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Op::And => '&',
+            Op::Or => '|',
+            Op::Xor => '^',
+            Op::Implies => '>',
+            Op::Iff => '=',
+        }
+    }
+
+    // Applies the connective to two truth values
+    pub fn apply(self, left: bool, right: bool) -> bool {
+        match self {
+            Op::And => left && right,
+            Op::Or => left || right,
+            Op::Xor => left ^ right,
+            Op::Implies => !left || right,
+            Op::Iff => left == right,
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        operator_precedence(self.symbol())
+    }
+}
+
+// Typed formula tree
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Expr {
+    Const(bool),
+    Var(Symbol),
+    Not(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn var(name: &str) -> Expr {
+        Expr::Var(Symbol::intern(name))
+    }
+
+    pub fn negate(expr: Expr) -> Expr {
+        Expr::Not(Box::new(expr))
+    }
 
-// AST Node Definition
+    pub fn binary(op: Op, left: Expr, right: Expr) -> Expr {
+        Expr::Binary(op, Box::new(left), Box::new(right))
+    }
+
+    pub fn and(left: Expr, right: Expr) -> Expr {
+        Expr::binary(Op::And, left, right)
+    }
+
+    pub fn or(left: Expr, right: Expr) -> Expr {
+        Expr::binary(Op::Or, left, right)
+    }
+}
+
+// Legacy char-based AST node, kept for compatibility; convert with Expr::try_from / ASTNode::from
 #[derive(Debug, PartialEq, Clone)]
 pub enum ASTNode {
     Operand(char),
     Operator(char, Box<ASTNode>, Option<Box<ASTNode>>),
 }
 
-pub fn get_ast(expression: &str) -> Result<ASTNode> {
-    let tokens = tokenize(expression);
+impl TryFrom<&ASTNode> for Expr {
+    type Error = FormulaError;
+
+    fn try_from(node: &ASTNode) -> Result<Expr, FormulaError> {
+        match node {
+            ASTNode::Operand('0') => Ok(Expr::Const(false)),
+            ASTNode::Operand('1') => Ok(Expr::Const(true)),
+            ASTNode::Operand(c) if c.is_alphabetic() => Ok(Expr::Var(Symbol::from(*c))),
+            ASTNode::Operand(c) => Err(FormulaError::MalformedNode(*c)),
+            ASTNode::Operator('!', operand, None) => Ok(Expr::negate(Expr::try_from(&**operand)?)),
+            ASTNode::Operator('!', _, Some(_)) => Err(FormulaError::MalformedNode('!')),
+            ASTNode::Operator(op, left, Some(right)) => {
+                let op = Op::from_symbol(*op).ok_or(FormulaError::UnknownOperator(*op))?;
+                Ok(Expr::binary(op, Expr::try_from(&**left)?, Expr::try_from(&**right)?))
+            }
+            ASTNode::Operator(op, _, _) => Err(FormulaError::MalformedNode(*op)),
+        }
+    }
+}
+
+impl TryFrom<ASTNode> for Expr {
+    type Error = FormulaError;
+
+    fn try_from(node: ASTNode) -> Result<Expr, FormulaError> {
+        Expr::try_from(&node)
+    }
+}
+
+// Only single-letter variables fit into the legacy char-based node
+impl TryFrom<&Expr> for ASTNode {
+    type Error = FormulaError;
+
+    fn try_from(expr: &Expr) -> Result<ASTNode, FormulaError> {
+        match expr {
+            Expr::Const(value) => Ok(ASTNode::Operand(if *value { '1' } else { '0' })),
+            Expr::Var(symbol) if symbol.is_single_letter() => {
+                Ok(ASTNode::Operand(symbol.name().chars().next().unwrap()))
+            }
+            Expr::Var(symbol) => Err(FormulaError::LegacyVariableName(symbol.name().to_string())),
+            Expr::Not(operand) => Ok(ASTNode::Operator('!', Box::new(ASTNode::try_from(&**operand)?), None)),
+            Expr::Binary(op, left, right) => Ok(ASTNode::Operator(
+                op.symbol(),
+                Box::new(ASTNode::try_from(&**left)?),
+                Some(Box::new(ASTNode::try_from(&**right)?)),
+            )),
+        }
+    }
+}
+
+impl TryFrom<Expr> for ASTNode {
+    type Error = FormulaError;
+
+    fn try_from(expr: Expr) -> Result<ASTNode, FormulaError> {
+        ASTNode::try_from(&expr)
+    }
+}
+
+pub fn parse_expr(expression: &str) -> Result<Expr, FormulaError> {
+    let tokens = tokenize(expression, Notation::Postfix)?;
     postfix_to_ast(&tokens)
 }
 
-fn postfix_to_ast(tokens: &[Token]) -> Result<ASTNode> {
-    let mut stack: Vec<ASTNode> = Vec::new();
+// Legacy entry point: parses a postfix formula into the char-based ASTNode.
+// Use parse_expr for multi-character variable names and structured errors.
+pub fn get_ast(expression: &str) -> anyhow::Result<ASTNode> {
+    Ok(ASTNode::try_from(parse_expr(expression)?)?)
+}
+
+// Legacy entry point for infix input, returning the char-based ASTNode like get_ast
+pub fn parse_infix(expression: &str) -> anyhow::Result<ASTNode> {
+    Ok(ASTNode::try_from(parse_infix_expr(expression)?)?)
+}
+
+// Parses a formula written in the given notation
+pub fn parse_formula(expression: &str, notation: Notation) -> Result<Expr, FormulaError> {
+    match notation {
+        Notation::Postfix => parse_expr(expression),
+        Notation::Infix => parse_infix_expr(expression),
+    }
+}
 
-    for token in tokens {
+// Parses an infix formula such as "(A & B) | !C" or "¬(A ∧ B) → C"
+pub fn parse_infix_expr(expression: &str) -> Result<Expr, FormulaError> {
+    let tokens = tokenize(expression, Notation::Infix)?;
+    if tokens.is_empty() {
+        return Err(FormulaError::EmptyFormula);
+    }
+    let mut parser = InfixParser { tokens: &tokens, pos: 0 };
+    let ast = parser.parse_expression(0)?;
+    match parser.peek() {
+        None => Ok(ast),
+        Some((offset, Token::RightParen)) => Err(FormulaError::UnbalancedParenthesis { offset: *offset }),
+        Some((offset, _)) => Err(FormulaError::UnexpectedToken { offset: *offset }),
+    }
+}
+
+fn postfix_to_ast(tokens: &[(usize, Token)]) -> Result<Expr, FormulaError> {
+    let mut stack: Vec<Expr> = Vec::new();
+
+    for (index, (offset, token)) in tokens.iter().enumerate() {
+        let underflow = |operator| FormulaError::StackUnderflow { token: index, operator };
         match token {
-            Token::Operand(value) => stack.push(ASTNode::Operand(*value)),
+            Token::Const(value) => stack.push(Expr::Const(*value)),
+            Token::Var(symbol) => stack.push(Expr::Var(*symbol)),
+            Token::Operator('!') => {
+                let operand = stack.pop().ok_or_else(|| underflow('!'))?;
+                stack.push(Expr::negate(operand));
+            }
             Token::Operator(op) => {
-                match *op {
-                    '!' => {
-                        let operand = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for NOT operand"))?;
-                        stack.push(ASTNode::Operator('!', Box::new(operand), None));
-                    }
-                    '&' | '|' => {
-                        let right = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for right operand"))?;
-                        let left = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for left operand"))?;
-                        stack.push(ASTNode::Operator(*op, Box::new(left), Some(Box::new(right))));
-                    }
-                    '>' => {
-                        // A > B -> !A | B
-                        let right = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for right operand"))?;
-                        let left = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for left operand"))?;
-                        let not_left = ASTNode::Operator('!', Box::new(left), None);
-                        stack.push(ASTNode::Operator('|', Box::new(not_left), Some(Box::new(right))));
-                    }
-                    '=' => {
-                        // A = B -> (A & B) | (!A & !B)
-                        let right = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for right operand"))?;
-                        let left = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for left operand"))?;
-                        let left_and_right = ASTNode::Operator('&', Box::new(left.clone()), Some(Box::new(right.clone())));
-                        let not_left = ASTNode::Operator('!', Box::new(left), None);
-                        let not_right = ASTNode::Operator('!', Box::new(right), None);
-                        let not_left_and_not_right = ASTNode::Operator('&', Box::new(not_left), Some(Box::new(not_right)));
-                        stack.push(ASTNode::Operator('|', Box::new(left_and_right), Some(Box::new(not_left_and_not_right))));
-                    }
-                    '^' => {
-                        // A ^ B -> (A & !B) | (!A & B)
-                        let right = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for right operand"))?;
-                        let left = stack.pop().ok_or_else(|| anyhow!("Failed to pop from stack for left operand"))?;
-                        let not_right = ASTNode::Operator('!', Box::new(right.clone()), None);
-                        let not_left = ASTNode::Operator('!', Box::new(left.clone()), None);
-                        let left_and_not_right = ASTNode::Operator('&', Box::new(left), Some(Box::new(not_right)));
-                        let not_left_and_right = ASTNode::Operator('&', Box::new(not_left), Some(Box::new(right)));
-                        stack.push(ASTNode::Operator('|', Box::new(left_and_not_right), Some(Box::new(not_left_and_right))));
-                    }
-                    _ => return Err(anyhow!("Unexpected operator {}", op)),
-                }
+                let right = stack.pop().ok_or_else(|| underflow(*op))?;
+                let left = stack.pop().ok_or_else(|| underflow(*op))?;
+                stack.push(build_binary(*op, left, right)?);
+            }
+            Token::LeftParen | Token::RightParen => {
+                return Err(FormulaError::UnexpectedToken { offset: *offset });
             }
         }
     }
 
-    if stack.len() == 1 {
-        Ok(stack.pop().unwrap())
-    } else {
-        Err(anyhow!("Failed to generate AST from postfix expression"))
+    match stack.len() {
+        0 => Err(FormulaError::EmptyFormula),
+        1 => Ok(stack.pop().unwrap()),
+        count => Err(FormulaError::LeftoverOperands { count }),
+    }
+}
+
+// Builds a binary operator node, keeping '>', '=' and '^' as written
+fn build_binary(op: char, left: Expr, right: Expr) -> Result<Expr, FormulaError> {
+    let op = Op::from_symbol(op).ok_or(FormulaError::UnknownOperator(op))?;
+    Ok(Expr::binary(op, left, right))
+}
+
+// Maps an operator character, including its Unicode alternative, to the ASCII form
+fn normalize_operator(ch: char) -> Option<char> {
+    match ch {
+        '!' | '¬' => Some('!'),
+        '&' | '∧' => Some('&'),
+        '|' | '∨' => Some('|'),
+        '^' | '⊕' => Some('^'),
+        '>' | '→' => Some('>'),
+        '=' | '↔' => Some('='),
+        _ => None,
     }
 }
 
-// Convert the input string to a list of tokens
-fn tokenize(expression: &str) -> Vec<Token> {
+// Convert the input string to a list of tokens paired with their byte offsets.
+// "[name]" is a variable with any name; in postfix every other letter is its own variable,
+// while infix reads runs of letters, digits and '_' as one identifier.
+// Whitespace and parentheses are only accepted in infix notation.
+fn tokenize(expression: &str, notation: Notation) -> Result<Vec<(usize, Token)>, FormulaError> {
+    let infix = notation == Notation::Infix;
     let mut tokens = Vec::new();
-    for ch in expression.chars() {
-        if ch.is_alphabetic() || ch == '0' || ch == '1' {
-            tokens.push(Token::Operand(ch));
-        } else if "!&|^>=".contains(ch) {
-            tokens.push(Token::Operator(ch));
+    let mut chars = expression.char_indices().peekable();
+    while let Some((offset, ch)) = chars.next() {
+        let token = if ch == '[' {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, ']')) => break,
+                    Some((_, c)) => name.push(c),
+                    None => return Err(FormulaError::InvalidIdentifier { offset }),
+                }
+            }
+            if name.is_empty() {
+                return Err(FormulaError::InvalidIdentifier { offset });
+            }
+            Token::Var(Symbol::try_intern(&name)?)
+        } else if infix && (ch.is_alphabetic() || ch == '_') {
+            let mut name = ch.to_string();
+            while let Some(&(_, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            Token::Var(Symbol::try_intern(&name)?)
+        } else if ch.is_alphabetic() {
+            Token::Var(Symbol::try_intern(ch.encode_utf8(&mut [0; 4]))?)
+        } else if ch == '0' || ch == '1' {
+            Token::Const(ch == '1')
+        } else if let Some(op) = normalize_operator(ch) {
+            Token::Operator(op)
+        } else if infix && ch == '(' {
+            Token::LeftParen
+        } else if infix && ch == ')' {
+            Token::RightParen
+        } else if infix && ch.is_whitespace() {
+            continue;
         } else {
-            panic!("Unexpected character in expression: {}", ch);
+            return Err(FormulaError::UnexpectedCharacter { ch, offset });
+        };
+        tokens.push((offset, token));
+    }
+    Ok(tokens)
+}
+
+// Precedence climbing parser over infix tokens.
+// Binary operators of equal precedence associate to the left, matching expr_to_infix_string.
+struct InfixParser<'a> {
+    tokens: &'a [(usize, Token)],
+    pos: usize,
+}
+
+impl InfixParser<'_> {
+    fn peek(&self) -> Option<&(usize, Token)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&(usize, Token)> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Expr, FormulaError> {
+        let mut left = self.parse_unary()?;
+        while let Some((_, Token::Operator(op))) = self.peek() {
+            let op = *op;
+            let precedence = operator_precedence(op);
+            if op == '!' || precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            let right = self.parse_expression(precedence + 1)?;
+            left = build_binary(op, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FormulaError> {
+        match self.next() {
+            Some((_, Token::Const(value))) => Ok(Expr::Const(*value)),
+            Some((_, Token::Var(symbol))) => Ok(Expr::Var(*symbol)),
+            Some((_, Token::Operator('!'))) => {
+                let operand = self.parse_unary()?;
+                Ok(Expr::negate(operand))
+            }
+            Some((offset, Token::LeftParen)) => {
+                let offset = *offset;
+                let inner = self.parse_expression(0)?;
+                match self.next() {
+                    Some((_, Token::RightParen)) => Ok(inner),
+                    _ => Err(FormulaError::UnbalancedParenthesis { offset }),
+                }
+            }
+            Some((offset, _)) => Err(FormulaError::UnexpectedToken { offset: *offset }),
+            None => Err(FormulaError::UnexpectedEnd),
         }
     }
-    tokens
 }
 
 // Implement fmt::Display to convert AST to a postfix string
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Const(value) => write!(f, "{}", if *value { '1' } else { '0' }),
+            Expr::Var(symbol) if symbol.is_single_letter() => write!(f, "{}", symbol),
+            Expr::Var(symbol) => write!(f, "[{}]", symbol),
+            Expr::Not(operand) => write!(f, "{}!", operand),
+            Expr::Binary(op, left, right) => write!(f, "{}{}{}", left, right, op.symbol()),
+        }
+    }
+}
+
 impl fmt::Display for ASTNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -144,6 +424,58 @@ pub fn ast_to_infix_string(ast: &ASTNode) -> String {
     }
 }
 
+// Converts the Expr back into a postfix expression string
+pub fn expr_to_postfix_string(ast: &Expr) -> String {
+    format!("{}", ast)
+}
+
+// Converts the AST to a string in the given notation
+pub fn ast_to_string(ast: &Expr, notation: Notation) -> String {
+    match notation {
+        Notation::Postfix => expr_to_postfix_string(ast),
+        Notation::Infix => expr_to_infix_string(ast),
+    }
+}
+
+// Converts the Expr to an infix string, taking operator precedence into account
+pub fn expr_to_infix_string(ast: &Expr) -> String {
+    match ast {
+        Expr::Const(_) => ast.to_string(),
+        Expr::Var(symbol) if symbol.is_identifier() => symbol.to_string(),
+        Expr::Var(symbol) => format!("[{}]", symbol),
+        Expr::Not(operand) => match expr_precedence(operand) {
+            Some(precedence) if precedence < operator_precedence('!') => {
+                format!("!({})", expr_to_infix_string(operand))
+            }
+            _ => format!("!{}", expr_to_infix_string(operand)),
+        },
+        Expr::Binary(op, left, right) => {
+            let current_precedence = op.precedence();
+
+            let left_str = match expr_precedence(left) {
+                Some(precedence) if precedence < current_precedence => format!("({})", expr_to_infix_string(left)),
+                _ => expr_to_infix_string(left),
+            };
+
+            let right_str = match expr_precedence(right) {
+                Some(precedence) if precedence <= current_precedence => format!("({})", expr_to_infix_string(right)),
+                _ => expr_to_infix_string(right),
+            };
+
+            format!("{} {} {}", left_str, op.symbol(), right_str)
+        }
+    }
+}
+
+// Precedence of the node's top operator; operands never need parentheses
+fn expr_precedence(ast: &Expr) -> Option<u8> {
+    match ast {
+        Expr::Const(_) | Expr::Var(_) => None,
+        Expr::Not(_) => Some(operator_precedence('!')),
+        Expr::Binary(op, _, _) => Some(op.precedence()),
+    }
+}
+
 fn operator_precedence(op: char) -> u8 {
     match op {
         '!' => 3,  // NOT has the highest precedence
@@ -153,4 +485,141 @@ fn operator_precedence(op: char) -> u8 {
         '>' | '=' => 0, // Implication and equivalence have the lowest precedence
         _ => 0,
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_infix() {
+        assert_eq!(parse_infix_expr("A").unwrap(), parse_expr("A").unwrap());
+        assert_eq!(parse_infix_expr("!A").unwrap(), parse_expr("A!").unwrap());
+        assert_eq!(parse_infix_expr("A & B | C").unwrap(), parse_expr("AB&C|").unwrap());
+        assert_eq!(parse_infix_expr("A & (B | C)").unwrap(), parse_expr("ABC|&").unwrap());
+        assert_eq!(parse_infix_expr("!(A | B)").unwrap(), parse_expr("AB|!").unwrap());
+        assert_eq!(parse_infix_expr("A | B | C").unwrap(), parse_expr("AB|C|").unwrap());
+        assert_eq!(parse_infix_expr("A | (B | C)").unwrap(), parse_expr("ABC||").unwrap());
+        assert_eq!(parse_infix_expr("A > B").unwrap(), parse_expr("AB>").unwrap());
+        assert_eq!(parse_infix_expr("A = B").unwrap(), parse_expr("AB=").unwrap());
+        assert_eq!(parse_infix_expr("A ^ B").unwrap(), parse_expr("AB^").unwrap());
+        assert_eq!(parse_infix_expr("1 & 0").unwrap(), parse_expr("10&").unwrap());
+
+        // 유니코드 연산자
+        assert_eq!(parse_infix_expr("¬(A ∧ B) ∨ C").unwrap(), parse_expr("AB&!C|").unwrap());
+        assert_eq!(parse_infix_expr("A → B ↔ C").unwrap(), parse_expr("AB>C=").unwrap());
+        assert_eq!(parse_infix_expr("A ⊕ B").unwrap(), parse_expr("AB^").unwrap());
+
+        // 잘못된 입력 처리
+        assert!(parse_infix_expr("").is_err());
+        assert!(parse_infix_expr("A &").is_err());
+        assert!(parse_infix_expr("(A | B").is_err());
+        assert!(parse_infix_expr("A | B)").is_err());
+        assert!(parse_infix_expr("A B").is_err());
+        assert!(parse_infix_expr("A $ B").is_err());
+    }
+
+    #[test]
+    fn test_formula_errors() {
+        assert_eq!(parse_expr(""), Err(FormulaError::EmptyFormula));
+        assert_eq!(parse_expr("AB&C"), Err(FormulaError::LeftoverOperands { count: 2 }));
+        assert_eq!(parse_expr("A&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+        assert_eq!(parse_expr("!"), Err(FormulaError::StackUnderflow { token: 0, operator: '!' }));
+        assert_eq!(parse_expr("AB$"), Err(FormulaError::UnexpectedCharacter { ch: '$', offset: 2 }));
+        assert_eq!(parse_expr("A B&"), Err(FormulaError::UnexpectedCharacter { ch: ' ', offset: 1 }));
+        assert_eq!(parse_expr("¬A2"), Err(FormulaError::UnexpectedCharacter { ch: '2', offset: 3 }));
+        assert_eq!(parse_expr("A[x1"), Err(FormulaError::InvalidIdentifier { offset: 1 }));
+        assert_eq!(parse_expr("A[]&"), Err(FormulaError::InvalidIdentifier { offset: 1 }));
+
+        assert_eq!(parse_infix_expr("  "), Err(FormulaError::EmptyFormula));
+        assert_eq!(parse_infix_expr("A &"), Err(FormulaError::UnexpectedEnd));
+        assert_eq!(parse_infix_expr("(A | B"), Err(FormulaError::UnbalancedParenthesis { offset: 0 }));
+        assert_eq!(parse_infix_expr("A | B)"), Err(FormulaError::UnbalancedParenthesis { offset: 5 }));
+        assert_eq!(parse_infix_expr("A B"), Err(FormulaError::UnexpectedToken { offset: 2 }));
+        assert_eq!(parse_infix_expr("A & | B"), Err(FormulaError::UnexpectedToken { offset: 4 }));
+    }
+
+    #[test]
+    fn test_legacy_entry_points() {
+        // get_ast와 parse_infix는 예전처럼 ASTNode를 반환
+        let not_a = ASTNode::Operator('!', Box::new(ASTNode::Operand('A')), None);
+        let node = ASTNode::Operator('|', Box::new(not_a), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(get_ast("A!B|").unwrap(), node);
+        assert_eq!(parse_infix("!A | B").unwrap(), node);
+        assert_eq!(ast_to_postfix_string(&node), "A!B|");
+        assert_eq!(ast_to_infix_string(&node), "!A | B");
+        assert_eq!(ast_to_infix_string(&get_ast("AB|C&!").unwrap()), "!((A | B) & C)");
+
+        // 에러는 anyhow::Error 안의 FormulaError
+        let error = get_ast("A&").unwrap_err();
+        assert_eq!(error.downcast_ref::<FormulaError>(), Some(&FormulaError::StackUnderflow { token: 1, operator: '&' }));
+        let error = parse_infix("x1 & B").unwrap_err();
+        assert_eq!(error.downcast_ref::<FormulaError>(), Some(&FormulaError::LegacyVariableName("x1".to_string())));
+    }
+
+    #[test]
+    fn test_legacy_conversion() {
+        let legacy = ASTNode::Operator(
+            '&',
+            Box::new(ASTNode::Operand('A')),
+            Some(Box::new(ASTNode::Operator('!', Box::new(ASTNode::Operand('1')), None))),
+        );
+        let expr = Expr::try_from(&legacy).unwrap();
+        assert_eq!(expr, Expr::and(Expr::var("A"), Expr::negate(Expr::Const(true))));
+        assert_eq!(ASTNode::try_from(&expr), Ok(legacy.clone()));
+        assert_eq!(legacy.to_string(), expr.to_string());
+
+        // 잘못된 모양의 노드는 변환할 수 없음
+        let not_with_right = ASTNode::Operator('!', Box::new(ASTNode::Operand('A')), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(Expr::try_from(not_with_right), Err(FormulaError::MalformedNode('!')));
+        let and_without_right = ASTNode::Operator('&', Box::new(ASTNode::Operand('A')), None);
+        assert_eq!(Expr::try_from(and_without_right), Err(FormulaError::MalformedNode('&')));
+        let unknown = ASTNode::Operator('?', Box::new(ASTNode::Operand('A')), Some(Box::new(ASTNode::Operand('B'))));
+        assert_eq!(Expr::try_from(unknown), Err(FormulaError::UnknownOperator('?')));
+        assert_eq!(Expr::try_from(ASTNode::Operand('$')), Err(FormulaError::MalformedNode('$')));
+        assert_eq!(ASTNode::try_from(Expr::var("x1")), Err(FormulaError::LegacyVariableName("x1".to_string())));
+    }
+
+    #[test]
+    fn test_named_variables() {
+        let expected = Expr::and(Expr::var("x17"), Expr::negate(Expr::var("door_open")));
+        assert_eq!(parse_expr("[x17][door_open]!&").unwrap(), expected);
+        assert_eq!(parse_infix_expr("x17 & !door_open").unwrap(), expected);
+        assert_eq!(parse_infix_expr("[x17] ∧ ¬[door_open]").unwrap(), expected);
+        assert_eq!(expr_to_postfix_string(&expected), "[x17][door_open]!&");
+        assert_eq!(expr_to_infix_string(&expected), "x17 & !door_open");
+
+        // 단일 문자는 후위 표기식에서 각각의 변수
+        assert_eq!(parse_expr("AB&").unwrap(), Expr::and(Expr::var("A"), Expr::var("B")));
+        assert_eq!(parse_expr("A[A]&").unwrap(), Expr::and(Expr::var("A"), Expr::var("A")));
+        assert_eq!(parse_infix_expr("AB").unwrap(), Expr::var("AB"));
+
+        // 식별자로 쓸 수 없는 이름은 괄호로 감싸서 출력
+        let odd = Expr::or(Expr::var("door-open"), Expr::var("7"));
+        assert_eq!(expr_to_infix_string(&odd), "[door-open] | [7]");
+        assert_eq!(parse_infix_expr(&expr_to_infix_string(&odd)).unwrap(), odd);
+        assert_eq!(parse_expr(&expr_to_postfix_string(&odd)).unwrap(), odd);
+    }
+
+    #[test]
+    fn test_postfix_round_trip() {
+        let formulas = ["A", "A!", "AB&", "AB|", "AB^", "AB>", "AB=", "AB=C=", "ABC==!", "AB>C^D=", "10>"];
+        for formula in formulas {
+            let ast = parse_expr(formula).unwrap();
+            assert_eq!(expr_to_postfix_string(&ast), formula);
+        }
+    }
+
+    #[test]
+    fn test_infix_round_trip() {
+        let formulas = [
+            "A", "A!", "AB&", "AB|C&", "AB&C|", "ABC|&", "ABC&|", "AB|!C!&", "A!!",
+            "AB&!", "ABC||!", "AB>", "AB=", "AB^", "AB^C^", "ABC^^", "AB>C>", "ABC==",
+        ];
+        for formula in formulas {
+            let ast = parse_expr(formula).unwrap();
+            let infix = expr_to_infix_string(&ast);
+            assert_eq!(parse_infix_expr(&infix).unwrap(), ast, "round trip of {}", infix);
+        }
+    }
+}
@@ -1,59 +1,144 @@
-use anyhow::{Result, Context};
-use super::ast::{get_ast, ASTNode};
+use super::assignment::Assignment;
+use super::ast::{parse_formula, Expr, Notation, Op};
+use super::error::FormulaError;
+use super::symbol::Symbol;
 
 pub fn eval_formula(formula: &str) -> bool {
-    check_eval_formula(formula).unwrap()
+    eval_formula_with_notation(formula, Notation::Postfix)
 }
 
-/// 수식을 평가하는 함수
-fn check_eval_formula(formula: &str) -> Result<bool> {
-    let ast = get_ast(formula).context("Failed to parse formula")?;
-    Ok(evaluate_ast(&ast))
+/// 주어진 표기법으로 작성된 수식을 평가하는 함수
+pub fn eval_formula_with_notation(formula: &str, notation: Notation) -> bool {
+    try_eval_formula_with_notation(formula, notation).unwrap_or_else(|e| panic!("Failed to evaluate formula: {}", e))
 }
 
-pub fn evaluate_ast(node: &ASTNode) -> bool {
+/// 수식을 평가하는 함수 (패닉 대신 에러를 반환)
+pub fn try_eval_formula(formula: &str) -> Result<bool, FormulaError> {
+    try_eval_formula_with_notation(formula, Notation::Postfix)
+}
+
+pub fn try_eval_formula_with_notation(formula: &str, notation: Notation) -> Result<bool, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    try_evaluate_ast(&ast)
+}
+
+pub fn evaluate_ast(node: &Expr) -> bool {
+    try_evaluate_ast(node).unwrap_or_else(|e| panic!("Failed to evaluate AST: {}", e))
+}
+
+/// 상수(0, 1)로만 이루어진 AST를 평가하는 함수
+pub fn try_evaluate_ast(node: &Expr) -> Result<bool, FormulaError> {
+    eval_with(node, &Assignment::new())
+}
+
+/// 논리 연산을 정의하는 값의 영역 (진리값, 집합 등)
+/// 같은 AST 순회를 진리표, SAT, 집합 평가에서 함께 사용하기 위한 trait
+pub trait BooleanAlgebra {
+    type Value;
+
+    fn constant(&self, value: bool) -> Self::Value;
+    fn variable(&self, var: Symbol) -> Result<Self::Value, FormulaError>;
+    fn not(&self, value: Self::Value) -> Self::Value;
+    fn binary(&self, op: Op, left: Self::Value, right: Self::Value) -> Self::Value;
+}
+
+// 진리값 영역: 변수 값은 Assignment에서 찾음
+impl BooleanAlgebra for Assignment {
+    type Value = bool;
+
+    fn constant(&self, value: bool) -> bool {
+        value
+    }
+
+    fn variable(&self, var: Symbol) -> Result<bool, FormulaError> {
+        self.get(var).ok_or_else(|| FormulaError::UnboundVariable(var.name().to_string()))
+    }
+
+    fn not(&self, value: bool) -> bool {
+        !value
+    }
+
+    fn binary(&self, op: Op, left: bool, right: bool) -> bool {
+        op.apply(left, right)
+    }
+}
+
+/// 주어진 영역에서 AST를 평가하는 함수
+pub fn evaluate_in<A: BooleanAlgebra>(node: &Expr, algebra: &A) -> Result<A::Value, FormulaError> {
     match node {
-        ASTNode::Operand(c) => match c {
-            '0' => false,  // '0'은 false를 나타냄
-            '1' => true,   // '1'은 true를 나타냄
-            _ => panic!("Unexpected operand: {}", c),  // 예상하지 못한 피연산자일 경우 패닉 발생
-        },
-        ASTNode::Operator('!', left, _) => {
-            // 논리 NOT 연산자는 단항 연산자이므로 왼쪽만 평가
-            let val = evaluate_ast(left);
-            !val
+        Expr::Const(value) => Ok(algebra.constant(*value)),
+        Expr::Var(var) => algebra.variable(*var),
+        Expr::Not(operand) => Ok(algebra.not(evaluate_in(operand, algebra)?)),
+        Expr::Binary(op, left, right) => {
+            let left_val = evaluate_in(left, algebra)?;
+            let right_val = evaluate_in(right, algebra)?;
+            Ok(algebra.binary(*op, left_val, right_val))
         }
-        ASTNode::Operator(op, left, right_opt) => {
-            let left_val = evaluate_ast(left);
+    }
+}
 
-            // `right_opt`이 `Some`일 경우에는 `right`를 평가하고, 그렇지 않으면 오류 발생
-            let right_val = match right_opt {
-                Some(right) => evaluate_ast(right), // `Some`일 때 `right`를 평가
-                None => panic!("Missing right operand for operator '{}'", op), // `None`일 때 패닉 발생
-            };
+/// 변수 할당에 따라 AST를 평가하는 함수 (값이 없는 변수는 에러)
+pub fn eval_with(node: &Expr, assignment: &Assignment) -> Result<bool, FormulaError> {
+    evaluate_in(node, assignment)
+}
 
-            calculate(*op, left_val, right_val)
+/// 일부 변수만 할당된 경우, 할당된 값을 대입하고 상수를 정리한 나머지 수식을 반환하는 함수
+pub fn partial_eval(node: &Expr, assignment: &Assignment) -> Expr {
+    match node {
+        Expr::Const(_) => node.clone(),
+        Expr::Var(var) => match assignment.get(*var) {
+            Some(value) => Expr::Const(value),
+            None => node.clone(),
+        },
+        Expr::Not(operand) => match partial_eval(operand, assignment) {
+            Expr::Const(value) => Expr::Const(!value),
+            residual => Expr::negate(residual),
+        },
+        Expr::Binary(op, left, right) => {
+            simplify_binary(*op, partial_eval(left, assignment), partial_eval(right, assignment))
         }
     }
 }
 
+// 한쪽 피연산자가 상수인 이항 연산을 정리
+fn simplify_binary(op: Op, left: Expr, right: Expr) -> Expr {
+    match (op, left, right) {
+        (op, Expr::Const(l), Expr::Const(r)) => Expr::Const(op.apply(l, r)),
+        (Op::And, Expr::Const(false), _) | (Op::And, _, Expr::Const(false)) => Expr::Const(false),
+        (Op::And, Expr::Const(true), other) | (Op::And, other, Expr::Const(true)) => other,
+        (Op::Or, Expr::Const(true), _) | (Op::Or, _, Expr::Const(true)) => Expr::Const(true),
+        (Op::Or, Expr::Const(false), other) | (Op::Or, other, Expr::Const(false)) => other,
+        (Op::Xor, Expr::Const(false), other) | (Op::Xor, other, Expr::Const(false)) => other,
+        (Op::Xor, Expr::Const(true), other) | (Op::Xor, other, Expr::Const(true)) => Expr::negate(other),
+        (Op::Iff, Expr::Const(true), other) | (Op::Iff, other, Expr::Const(true)) => other,
+        (Op::Iff, Expr::Const(false), other) | (Op::Iff, other, Expr::Const(false)) => Expr::negate(other),
+        (Op::Implies, Expr::Const(false), _) | (Op::Implies, _, Expr::Const(true)) => Expr::Const(true),
+        (Op::Implies, Expr::Const(true), other) => other,
+        (Op::Implies, other, Expr::Const(false)) => Expr::negate(other),
+        (op, left, right) => Expr::binary(op, left, right),
+    }
+}
+
 /// 주어진 연산자와 두 피연산자 값을 사용해 논리 연산을 수행하는 함수
 pub fn calculate(op: char, left_val: bool, right_val: bool) -> bool {
+    try_calculate(op, left_val, right_val).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_calculate(op: char, left_val: bool, right_val: bool) -> Result<bool, FormulaError> {
     match op {
-        '&' => left_val && right_val, // AND 연산자
-        '|' => left_val || right_val, // OR 연산자
-        '^' => left_val ^ right_val,  // XOR 연산자
-        '!' => !left_val,             // NOT 연산자 (단항 연산자)
-        '>' => !left_val || right_val, // IMPL 연산자 (논리적 함의) : A->B = !A | B
-        '=' => left_val == right_val, // EQV 연산자
-        _ => panic!("Unexpected operator: {}", op),  // 예상하지 못한 연산자는 패닉을 발생시킴
+        '!' => Ok(!left_val), // NOT 연산자 (단항 연산자)
+        _ => Op::from_symbol(op)
+            .map(|op| op.apply(left_val, right_val))
+            .ok_or(FormulaError::UnknownOperator(op)),  // 예상하지 못한 연산자
     }
 }
 
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use crate::ex03::ast::{parse_expr, expr_to_postfix_string};
 
     #[test]
     fn test_eval_formula() {
@@ -94,4 +179,61 @@ mod tests {
         assert_eq!(eval_formula("01&1&1&"), false);
         assert_eq!(eval_formula("0111&&&"), false);
     }    
+
+    #[test]
+    fn eval_infix() {
+        assert_eq!(eval_formula_with_notation("1 & 0", Notation::Infix), false);
+        assert_eq!(eval_formula_with_notation("1 & 0 | 1", Notation::Infix), true);
+        assert_eq!(eval_formula_with_notation("1 & (0 | 0)", Notation::Infix), false);
+        assert_eq!(eval_formula_with_notation("¬1 ∨ (1 → 0)", Notation::Infix), false);
+        assert_eq!(eval_formula_with_notation("1 ⊕ 1 ↔ 0", Notation::Infix), true);
+        assert_eq!(eval_formula_with_notation("10|", Notation::Postfix), true);
+    }
+
+    #[test]
+    fn eval_errors() {
+        assert_eq!(try_eval_formula("10&"), Ok(false));
+        assert_eq!(try_eval_formula("1A&"), Err(FormulaError::UnboundVariable("A".to_string())));
+        assert_eq!(try_eval_formula("1&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+        assert_eq!(try_eval_formula("101&"), Err(FormulaError::LeftoverOperands { count: 2 }));
+        assert_eq!(try_eval_formula_with_notation("1 & (0", Notation::Infix), Err(FormulaError::UnbalancedParenthesis { offset: 4 }));
+        assert_eq!(try_calculate('?', true, false), Err(FormulaError::UnknownOperator('?')));
+        assert_eq!(try_evaluate_ast(&Expr::negate(Expr::var("B"))), Err(FormulaError::UnboundVariable("B".to_string())));
+    }
+
+    #[test]
+    fn eval_with_assignment() {
+        let ast = parse_expr("AB&C|").unwrap();
+        let assignment = Assignment::new().with('A', true).with('B', true).with('C', false);
+        assert_eq!(eval_with(&ast, &assignment), Ok(true));
+        let assignment = assignment.with('B', false);
+        assert_eq!(eval_with(&ast, &assignment), Ok(false));
+
+        let partial = Assignment::new().with('A', true);
+        assert_eq!(eval_with(&ast, &partial), Err(FormulaError::UnboundVariable("B".to_string())));
+    }
+
+    #[test]
+    fn partial_evaluation() {
+        let residual = |formula: &str, assignment: &Assignment| {
+            expr_to_postfix_string(&partial_eval(&parse_expr(formula).unwrap(), assignment))
+        };
+        let a_true = Assignment::new().with('A', true);
+        let a_false = Assignment::new().with('A', false);
+
+        assert_eq!(residual("AB&C|", &a_true), "BC|");
+        assert_eq!(residual("AB&C|", &a_false), "C");
+        assert_eq!(residual("AB|", &a_true), "1");
+        assert_eq!(residual("AB^", &a_true), "B!");
+        assert_eq!(residual("AB=", &a_false), "B!");
+        assert_eq!(residual("AB>", &a_false), "1");
+        assert_eq!(residual("BA>", &a_false), "B!");
+        assert_eq!(residual("BA>", &a_true), "1");
+        assert_eq!(residual("A!B&", &a_false), "B");
+        assert_eq!(residual("[x1]A&", &a_true), "[x1]");
+
+        // 모든 변수가 할당되면 상수가 남음
+        let all = a_true.with('B', false);
+        assert_eq!(residual("AB&A|", &all), "1");
+    }
 }
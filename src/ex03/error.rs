@@ -0,0 +1,75 @@
+use std::fmt;
+
+// Errors produced anywhere in the formula pipeline (parsing, evaluation, normal forms)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FormulaError {
+    EmptyFormula,
+    UnexpectedCharacter { ch: char, offset: usize }, // offset is a byte offset into the input
+    StackUnderflow { token: usize, operator: char }, // token is the index of the offending postfix token
+    LeftoverOperands { count: usize },
+    UnknownOperator(char),
+    MalformedNode(char), // legacy ASTNode with the wrong number of operands or an invalid operand
+    LegacyVariableName(String), // multi-character name that does not fit into the char-based ASTNode
+    UnboundVariable(String),
+    InvalidIdentifier { offset: usize }, // unterminated or empty "[name]"
+    UnbalancedParenthesis { offset: usize },
+    UnexpectedToken { offset: usize },
+    UnexpectedEnd,
+    TooManyVariables { count: usize, limit: usize }, // exhaustive algorithms over the truth table
+    TooManyTerms { limit: usize }, // expanding into a sum of products exceeded the term limit
+    TooManyNodes { limit: usize }, // a BDD node index would not fit into its u32 handle
+    RowCount { expected: usize, found: usize }, // a result column does not have one value per truth table row
+    VariableIndex { index: usize, count: usize }, // a truth table column index past the last variable
+    MismatchedVariables, // combining truth tables whose variable lists differ
+    TooManySymbols { limit: usize }, // interning another variable name would exceed the interner's byte limit
+    AmbiguousSetIndex { index: usize, first: String, second: String }, // two variables name the same set, e.g. "A" and "x0"
+    InvalidModel(String), // a solver model or minimized formula failed its own verification; holds the rejected result
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaError::EmptyFormula => write!(f, "empty formula"),
+            FormulaError::UnexpectedCharacter { ch, offset } => {
+                write!(f, "unexpected character '{}' at byte {}", ch, offset)
+            }
+            FormulaError::StackUnderflow { token, operator } => {
+                write!(f, "missing operand for '{}' at token {}", operator, token)
+            }
+            FormulaError::LeftoverOperands { count } => {
+                write!(f, "{} operands left on the stack, expected 1", count)
+            }
+            FormulaError::UnknownOperator(op) => write!(f, "unknown operator '{}'", op),
+            FormulaError::MalformedNode(c) => write!(f, "malformed node '{}'", c),
+            FormulaError::LegacyVariableName(name) => {
+                write!(f, "variable '{}' cannot be stored in a single-character node", name)
+            }
+            FormulaError::UnboundVariable(var) => write!(f, "variable '{}' has no value", var),
+            FormulaError::InvalidIdentifier { offset } => write!(f, "invalid variable name at byte {}", offset),
+            FormulaError::UnbalancedParenthesis { offset } => {
+                write!(f, "unbalanced parenthesis at byte {}", offset)
+            }
+            FormulaError::UnexpectedToken { offset } => write!(f, "unexpected token at byte {}", offset),
+            FormulaError::UnexpectedEnd => write!(f, "unexpected end of formula"),
+            FormulaError::TooManyVariables { count, limit } => {
+                write!(f, "{} variables exceed the limit of {}", count, limit)
+            }
+            FormulaError::TooManyTerms { limit } => write!(f, "expansion exceeds {} product terms", limit),
+            FormulaError::TooManyNodes { limit } => write!(f, "BDD node index exceeds {}", limit),
+            FormulaError::AmbiguousSetIndex { index, first, second } => {
+                write!(f, "variables '{}' and '{}' both refer to set {}", first, second, index)
+            }
+            FormulaError::TooManySymbols { limit } => write!(f, "variable names exceed the limit of {} bytes", limit),
+            FormulaError::RowCount { expected, found } => {
+                write!(f, "expected {} truth table rows but found {}", expected, found)
+            }
+            FormulaError::VariableIndex { index, count } => {
+                write!(f, "variable index {} is out of range for {} variables", index, count)
+            }
+            FormulaError::MismatchedVariables => write!(f, "truth tables are over different variables"),
+            FormulaError::InvalidModel(model) => write!(f, "result failed verification: {}", model),
+        }
+    }
+}
+
+impl std::error::Error for FormulaError {}
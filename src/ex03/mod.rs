@@ -1,2 +1,5 @@
+pub mod error;
+pub mod symbol;
+pub mod assignment;
 pub mod boolean_evaluation;
 pub mod ast;
\ No newline at end of file
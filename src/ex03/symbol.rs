@@ -0,0 +1,280 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+use super::error::FormulaError;
+
+// Variable name. Generated names "x0", "x1", ... are stored as their number and never
+// touch the interner; every other name is interned once and compared by pointer.
+// Interned names live until the program ends, so the interner holds at most
+// MAX_INTERNED_BYTES of names; input from untrusted sources goes through try_intern.
+// Ordering is natural: the name without its trailing digits, then the number they form,
+// so sorted variable lists read x1, x2, x10.
+#[derive(Clone, Copy)]
+pub struct Symbol(Repr);
+
+#[derive(Clone, Copy)]
+enum Repr {
+    Interned(&'static str),
+    Numbered(u32), // "x{n}" without leading zeros
+}
+
+// Total length of all interned names
+pub const MAX_INTERNED_BYTES: usize = 1 << 24;
+
+struct Interner {
+    names: HashSet<&'static str>,
+    bytes: usize,
+}
+
+impl Interner {
+    fn new() -> Interner {
+        Interner { names: HashSet::new(), bytes: 0 }
+    }
+
+    fn intern(&mut self, name: &str, limit: usize) -> Result<&'static str, FormulaError> {
+        if let Some(&interned) = self.names.get(name) {
+            return Ok(interned);
+        }
+        if self.bytes + name.len() > limit {
+            return Err(FormulaError::TooManySymbols { limit });
+        }
+        // 이름은 프로그램이 끝날 때까지 유지되므로 leak해서 &'static str로 보관
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        self.names.insert(name);
+        self.bytes += name.len();
+        Ok(name)
+    }
+}
+
+fn interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(Interner::new()))
+}
+
+// "x17" -> 17. "x017"이나 u32를 넘는 수는 일반 이름으로 취급
+fn numbered(name: &str) -> Option<u32> {
+    let digits = name.strip_prefix('x')?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || (digits.len() > 1 && digits.starts_with('0')) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+// Whether a name can be written bare in infix notation, checked before interning
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+impl Symbol {
+    // Panics once the interner is full; use try_intern for names read from untrusted input
+    pub fn intern(name: &str) -> Symbol {
+        Symbol::try_intern(name).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_intern(name: &str) -> Result<Symbol, FormulaError> {
+        if let Some(number) = numbered(name) {
+            return Ok(Symbol::numbered(number));
+        }
+        let name = interner().lock().unwrap().intern(name, MAX_INTERNED_BYTES)?;
+        Ok(Symbol(Repr::Interned(name)))
+    }
+
+    // "x{number}" without interning
+    pub fn numbered(number: u32) -> Symbol {
+        Symbol(Repr::Numbered(number))
+    }
+
+    pub fn name(self) -> Cow<'static, str> {
+        match self.0 {
+            Repr::Interned(name) => Cow::Borrowed(name),
+            Repr::Numbered(number) => Cow::Owned(format!("x{}", number)),
+        }
+    }
+
+    // Postfix operands are single letters; any other name must be written as "[name]"
+    pub fn is_single_letter(self) -> bool {
+        let name = self.name();
+        let mut chars = name.chars();
+        matches!((chars.next(), chars.next()), (Some(c), None) if c.is_alphabetic())
+    }
+
+    // Infix identifiers: a letter or '_' followed by letters, digits or '_'
+    pub fn is_identifier(self) -> bool {
+        is_identifier(&self.name())
+    }
+
+    // Position of the variable in an indexed family: 'A'..'Z' map to 0..25,
+    // and a name ending in digits such as "x17" maps to that number
+    pub fn index(self) -> Option<usize> {
+        let name = match self.0 {
+            Repr::Numbered(number) => return Some(number as usize),
+            Repr::Interned(name) => name,
+        };
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if c.is_ascii_uppercase() {
+                return Some(c as usize - 'A' as usize);
+            }
+        }
+        let digits_start = name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if digits_start == name.len() {
+            return None;
+        }
+        name[digits_start..].parse().ok()
+    }
+}
+
+impl From<char> for Symbol {
+    fn from(c: char) -> Symbol {
+        Symbol::intern(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Symbol {
+        Symbol::intern(name)
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        match (self.0, other.0) {
+            (Repr::Interned(name), Repr::Interned(other)) => std::ptr::eq(name, other),
+            (Repr::Numbered(number), Repr::Numbered(other)) => number == other,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.0 {
+            Repr::Interned(name) => name.as_ptr().hash(state),
+            Repr::Numbered(number) => number.hash(state),
+        }
+    }
+}
+
+// Splits "x017" into ("x", "17"): the prefix and the trailing number without leading zeros
+fn natural_key(name: &str) -> (&str, &str) {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    (prefix, name[prefix.len()..].trim_start_matches('0'))
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Symbol) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        if let (Repr::Numbered(number), Repr::Numbered(other)) = (self.0, other.0) {
+            return number.cmp(&other);
+        }
+        let (name, other_name) = (self.name(), other.name());
+        let (prefix, number) = natural_key(&name);
+        let (other_prefix, other_number) = natural_key(&other_name);
+        // 숫자는 자릿수가 적을수록 작음. 같은 수("x1", "x01")는 이름 전체로 구분
+        prefix
+            .cmp(other_prefix)
+            .then(number.len().cmp(&other_number.len()))
+            .then(number.cmp(other_number))
+            .then(name.cmp(&other_name))
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Symbol) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Symbol({:?})", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        assert_eq!(Symbol::intern("door_open"), Symbol::intern("door_open"));
+        assert_ne!(Symbol::intern("x1"), Symbol::intern("x17"));
+        assert_eq!(Symbol::from('A'), Symbol::intern("A"));
+        assert_eq!(Symbol::intern("door_open").name(), "door_open");
+
+        let mut symbols = [Symbol::intern("b"), Symbol::intern("B"), Symbol::intern("a"), Symbol::intern("A")];
+        symbols.sort();
+        let names: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, vec!["A", "B", "a", "b"]);
+
+        // 끝의 숫자는 수로 비교
+        let mut symbols: Vec<Symbol> = ["x10", "x2", "y", "x1", "x", "x01", "x_9"].iter().map(|&name| Symbol::intern(name)).collect();
+        symbols.sort();
+        let names: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, vec!["x", "x01", "x1", "x2", "x10", "x_9", "y"]);
+    }
+
+    #[test]
+    fn test_numbered_symbols() {
+        // "x{n}"은 인터닝하지 않고 번호로 저장하지만 같은 이름의 Symbol과 구분되지 않음
+        assert_eq!(Symbol::intern("x17"), Symbol::numbered(17));
+        assert_eq!(Symbol::intern("x0"), Symbol::numbered(0));
+        assert_ne!(Symbol::intern("x017"), Symbol::numbered(17));
+        assert_eq!(Symbol::numbered(17).name(), "x17");
+        assert!(matches!(Symbol::intern("x17").name(), Cow::Owned(_)));
+        assert!(matches!(Symbol::intern("x017").name(), Cow::Borrowed(_)));
+        assert_eq!(Symbol::numbered(17).index(), Some(17));
+        assert!(Symbol::numbered(3).is_identifier());
+        assert!(!Symbol::numbered(3).is_single_letter());
+
+        let mut symbols = [Symbol::numbered(10), Symbol::intern("x01"), Symbol::intern("w"), Symbol::numbered(2)];
+        symbols[2] = Symbol::intern("x");
+        symbols.sort();
+        let names: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, vec!["x", "x01", "x2", "x10"]);
+        assert_eq!(Symbol::try_intern("x4294967296").unwrap().name(), "x4294967296");
+    }
+
+    #[test]
+    fn test_interner_limit() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("abc", 5), Ok("abc"));
+        assert_eq!(interner.intern("abc", 5), Ok("abc"));
+        assert_eq!(interner.intern("de", 5), Ok("de"));
+        assert_eq!(interner.intern("f", 5), Err(FormulaError::TooManySymbols { limit: 5 }));
+        assert_eq!(interner.bytes, 5);
+    }
+
+    #[test]
+    fn test_symbol_kinds() {
+        assert!(Symbol::from('A').is_single_letter());
+        assert!(!Symbol::intern("x1").is_single_letter());
+        assert!(Symbol::intern("door_open").is_identifier());
+        assert!(!Symbol::intern("door-open").is_identifier());
+        assert!(!Symbol::intern("1x").is_identifier());
+
+        assert_eq!(Symbol::from('A').index(), Some(0));
+        assert_eq!(Symbol::from('C').index(), Some(2));
+        assert_eq!(Symbol::intern("x17").index(), Some(17));
+        assert_eq!(Symbol::intern("7").index(), Some(7));
+        assert_eq!(Symbol::intern("door_open").index(), None);
+        assert_eq!(Symbol::from('a').index(), None);
+    }
+}
@@ -0,0 +1,414 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+use crate::ex03::ast::{Expr, Op};
+use crate::ex03::boolean_evaluation::{evaluate_in, BooleanAlgebra};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use super::truth_table::extract_and_sort_vars_from_ast;
+
+// 결과 열을 비트셋으로 저장하는 진리표
+// 행 i는 words[i / 64]의 (i % 64)번째 비트. 행 순서는 print_truth_table과 같음 (첫 번째 변수가 최상위 비트)
+// AST를 행마다 평가하지 않고, 64행짜리 변수 패턴 단어 위에서 한 번에 평가
+
+pub const MAX_TABLE_VARIABLES: usize = 30;
+
+// 한 번에 평가하는 단어 수. 변수가 많아도 중간 결과가 이 크기를 넘지 않음
+const BLOCK_WORDS: usize = 1024;
+
+// 단어 안에서 반복되는 하위 6개 비트 위치의 패턴
+const LOW_PATTERNS: [u64; 6] = [
+    0xAAAA_AAAA_AAAA_AAAA,
+    0xCCCC_CCCC_CCCC_CCCC,
+    0xF0F0_F0F0_F0F0_F0F0,
+    0xFF00_FF00_FF00_FF00,
+    0xFFFF_0000_FFFF_0000,
+    0xFFFF_FFFF_0000_0000,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruthTable {
+    vars: Vec<Symbol>,
+    words: Vec<u64>,
+}
+
+// 행 번호의 비트 bit가 1인 행들의 패턴 (word번째 단어)
+fn pattern(bit: usize, word: usize) -> u64 {
+    if bit < 6 {
+        LOW_PATTERNS[bit]
+    } else if (word >> (bit - 6)) & 1 == 1 {
+        u64::MAX
+    } else {
+        0
+    }
+}
+
+fn word_count(num_vars: usize) -> usize {
+    if num_vars <= 6 { 1 } else { 1 << (num_vars - 6) }
+}
+
+// 마지막 단어에서 실제 행에 해당하는 비트
+fn last_word_mask(num_vars: usize) -> u64 {
+    if num_vars >= 6 { u64::MAX } else { (1u64 << (1 << num_vars)) - 1 }
+}
+
+// start번째 단어부터 len개 단어를 평가하는 영역
+struct Block<'a> {
+    vars: &'a [Symbol],
+    start: usize,
+    len: usize,
+}
+
+impl BooleanAlgebra for Block<'_> {
+    type Value = Vec<u64>;
+
+    fn constant(&self, value: bool) -> Vec<u64> {
+        vec![if value { u64::MAX } else { 0 }; self.len]
+    }
+
+    fn variable(&self, var: Symbol) -> Result<Vec<u64>, FormulaError> {
+        let index = self
+            .vars
+            .iter()
+            .position(|&v| v == var)
+            .ok_or_else(|| FormulaError::UnboundVariable(var.name().to_string()))?;
+        let bit = self.vars.len() - 1 - index;
+        Ok((self.start..self.start + self.len).map(|word| pattern(bit, word)).collect())
+    }
+
+    fn not(&self, mut value: Vec<u64>) -> Vec<u64> {
+        value.iter_mut().for_each(|word| *word = !*word);
+        value
+    }
+
+    fn binary(&self, op: Op, mut left: Vec<u64>, right: Vec<u64>) -> Vec<u64> {
+        for (l, r) in left.iter_mut().zip(right) {
+            *l = apply_word(op, *l, r);
+        }
+        left
+    }
+}
+
+fn apply_word(op: Op, left: u64, right: u64) -> u64 {
+    match op {
+        Op::And => left & right,
+        Op::Or => left | right,
+        Op::Xor => left ^ right,
+        Op::Implies => !left | right,
+        Op::Iff => !(left ^ right),
+    }
+}
+
+fn check_table_size(vars: &[Symbol]) -> Result<(), FormulaError> {
+    if vars.len() > MAX_TABLE_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_TABLE_VARIABLES });
+    }
+    Ok(())
+}
+
+impl TruthTable {
+    // 식에 나타나는 변수(정렬된 순서)에 대한 진리표
+    pub fn from_ast(ast: &Expr) -> Result<TruthTable, FormulaError> {
+        TruthTable::from_ast_with_vars(ast, &extract_and_sort_vars_from_ast(ast))
+    }
+
+    // 주어진 열 순서로 평가. vars에 없는 변수가 식에 있으면 에러
+    pub fn from_ast_with_vars(ast: &Expr, vars: &[Symbol]) -> Result<TruthTable, FormulaError> {
+        check_table_size(vars)?;
+        let total = word_count(vars.len());
+        let mut words = Vec::with_capacity(total);
+        for start in (0..total).step_by(BLOCK_WORDS) {
+            let block = Block { vars, start, len: BLOCK_WORDS.min(total - start) };
+            words.extend(evaluate_in(ast, &block)?);
+        }
+        let mut table = TruthTable { vars: vars.to_vec(), words };
+        table.mask();
+        Ok(table)
+    }
+
+    // 모든 행이 value인 진리표
+    pub fn constant(vars: &[Symbol], value: bool) -> Result<TruthTable, FormulaError> {
+        check_table_size(vars)?;
+        let mut table = TruthTable { vars: vars.to_vec(), words: vec![if value { u64::MAX } else { 0 }; word_count(vars.len())] };
+        table.mask();
+        Ok(table)
+    }
+
+    // index번째 변수 열과 같은 진리표
+    pub fn variable(vars: &[Symbol], index: usize) -> Result<TruthTable, FormulaError> {
+        check_table_size(vars)?;
+        if index >= vars.len() {
+            return Err(FormulaError::VariableIndex { index, count: vars.len() });
+        }
+        let bit = vars.len() - 1 - index;
+        let words = (0..word_count(vars.len())).map(|word| pattern(bit, word)).collect();
+        let mut table = TruthTable { vars: vars.to_vec(), words };
+        table.mask();
+        Ok(table)
+    }
+
+    // 결과 열로 만듦. results[i]는 i번째 행의 값
+    pub fn from_bools(vars: &[Symbol], results: &[bool]) -> Result<TruthTable, FormulaError> {
+        let mut table = TruthTable::constant(vars, false)?;
+        if results.len() != table.len() {
+            return Err(FormulaError::RowCount { expected: table.len(), found: results.len() });
+        }
+        for (row, &result) in results.iter().enumerate() {
+            table.set(row, result);
+        }
+        Ok(table)
+    }
+
+    // 실제 행이 아닌 비트를 0으로 유지하여 같은 함수는 같은 words를 갖게 함
+    fn mask(&mut self) {
+        let mask = last_word_mask(self.vars.len());
+        if let Some(last) = self.words.last_mut() {
+            *last &= mask;
+        }
+    }
+
+    pub fn vars(&self) -> &[Symbol] {
+        &self.vars
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.vars.len()
+    }
+
+    // 행 수 (2^변수 수)
+    pub fn len(&self) -> usize {
+        1 << self.vars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn get(&self, row: usize) -> bool {
+        assert!(row < self.len(), "row {} out of range", row);
+        self.words[row / 64] >> (row % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, value: bool) {
+        assert!(row < self.len(), "row {} out of range", row);
+        if value {
+            self.words[row / 64] |= 1 << (row % 64);
+        } else {
+            self.words[row / 64] &= !(1 << (row % 64));
+        }
+    }
+
+    // 결과가 1인 행의 수
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    pub fn is_tautology(&self) -> bool {
+        self.count_ones() == self.len() as u64
+    }
+
+    pub fn is_contradiction(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn to_bools(&self) -> Vec<bool> {
+        (0..self.len()).map(|row| self.get(row)).collect()
+    }
+
+    // 같은 변수 순서의 두 진리표에 이항 연산자를 적용. 변수 목록이 다르면 panic
+    pub fn apply(&self, op: Op, other: &TruthTable) -> TruthTable {
+        assert_eq!(self.vars, other.vars, "truth tables over different variables");
+        self.apply_words(op, other)
+    }
+
+    // apply와 같지만 변수 목록이 다르면 에러
+    pub fn try_apply(&self, op: Op, other: &TruthTable) -> Result<TruthTable, FormulaError> {
+        if self.vars != other.vars {
+            return Err(FormulaError::MismatchedVariables);
+        }
+        Ok(self.apply_words(op, other))
+    }
+
+    fn apply_words(&self, op: Op, other: &TruthTable) -> TruthTable {
+        let words = self.words.iter().zip(&other.words).map(|(&l, &r)| apply_word(op, l, r)).collect();
+        let mut table = TruthTable { vars: self.vars.clone(), words };
+        table.mask();
+        table
+    }
+
+    // 뫼비우스 변환: 행 u의 값을 u에 포함되는 모든 행 v의 값의 XOR로 바꿈 (GF(2)에서 자기 역변환)
+    // 진리표와 대수 정규형(ANF) 계수 사이의 변환에 사용
+    pub fn mobius(&self) -> TruthTable {
+        let mut words = self.words.clone();
+        let n = self.vars.len();
+        for (bit, &pattern) in LOW_PATTERNS.iter().enumerate().take(n) {
+            let shift = 1 << bit;
+            for word in words.iter_mut() {
+                *word ^= (*word << shift) & pattern;
+            }
+        }
+        for bit in 6..n {
+            let stride = 1 << (bit - 6);
+            for index in 0..words.len() {
+                if index & stride != 0 {
+                    words[index] ^= words[index ^ stride];
+                }
+            }
+        }
+        let mut table = TruthTable { vars: self.vars.clone(), words };
+        table.mask();
+        table
+    }
+
+    pub fn negate(&self) -> TruthTable {
+        let mut table = TruthTable { vars: self.vars.clone(), words: self.words.iter().map(|word| !word).collect() };
+        table.mask();
+        table
+    }
+}
+
+impl Not for &TruthTable {
+    type Output = TruthTable;
+
+    fn not(self) -> TruthTable {
+        self.negate()
+    }
+}
+
+// &, |, ^는 apply를 쓰므로 변수 목록이 다르면 panic. 다를 수 있으면 try_apply 사용
+impl BitAnd for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitand(self, other: &TruthTable) -> TruthTable {
+        self.apply(Op::And, other)
+    }
+}
+
+impl BitOr for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitor(self, other: &TruthTable) -> TruthTable {
+        self.apply(Op::Or, other)
+    }
+}
+
+impl BitXor for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitxor(self, other: &TruthTable) -> TruthTable {
+        self.apply(Op::Xor, other)
+    }
+}
+
+// 결과 열을 행 순서대로: "0111"
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.len() {
+            write!(f, "{}", if self.get(row) { 1 } else { 0 })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::assignment::Assignment;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex03::boolean_evaluation::eval_with;
+
+    fn vars(names: &str) -> Vec<Symbol> {
+        names.chars().map(Symbol::from).collect()
+    }
+
+    #[test]
+    fn test_truth_table_bits() {
+        assert_eq!(TruthTable::from_ast(&parse_expr("AB&C|").unwrap()).unwrap().to_string(), "01010111");
+        assert_eq!(TruthTable::from_ast(&parse_expr("AB>").unwrap()).unwrap().to_string(), "1101");
+        assert_eq!(TruthTable::from_ast(&parse_expr("1").unwrap()).unwrap().to_string(), "1");
+        assert_eq!(TruthTable::from_ast(&parse_expr("A").unwrap()).unwrap().words(), &[0b10]);
+
+        // 행마다 평가한 결과와 같음 (단어 경계를 넘는 7, 8개 변수 포함)
+        for formula in ["AB^C=D>", "ABCDEFG^^^^^^", "AB&CD&|EF&|GH&|", "AB|C!&D=EF>|G^H&"] {
+            let ast = parse_expr(formula).unwrap();
+            let table = TruthTable::from_ast(&ast).unwrap();
+            let vars = extract_and_sort_vars_from_ast(&ast);
+            for row in 0..table.len() {
+                let expected = eval_with(&ast, &Assignment::from_row(&vars, row as u64)).unwrap();
+                assert_eq!(table.get(row), expected, "{} row {}", formula, row);
+            }
+        }
+
+        // 열 순서를 지정하면 같은 함수라도 비트 배치가 달라짐
+        let ast = parse_expr("AB!&").unwrap();
+        assert_eq!(TruthTable::from_ast_with_vars(&ast, &vars("AB")).unwrap().to_string(), "0010");
+        assert_eq!(TruthTable::from_ast_with_vars(&ast, &vars("BA")).unwrap().to_string(), "0100");
+        assert_eq!(TruthTable::from_ast_with_vars(&ast, &vars("ABC")).unwrap().to_string(), "00001100");
+        assert_eq!(
+            TruthTable::from_ast_with_vars(&ast, &vars("A")),
+            Err(FormulaError::UnboundVariable("B".to_string()))
+        );
+
+        // 24개 변수: 2^24행을 블록 단위의 AST 순회로 평가
+        let wide: Vec<Symbol> = (0..24).map(|i| Symbol::intern(&format!("x{:02}", i))).collect();
+        let parity = wide.iter().map(|&var| Expr::Var(var)).reduce(|acc, next| Expr::binary(Op::Xor, acc, next)).unwrap();
+        let table = TruthTable::from_ast(&parity).unwrap();
+        assert_eq!(table.len(), 1 << 24);
+        assert_eq!(table.count_ones(), 1 << 23);
+        assert!(table.get(1) && !table.get(3) && table.get((1 << 24) - 2));
+        let too_wide: Vec<Symbol> = (0..31).map(|i| Symbol::intern(&format!("x{:02}", i))).collect();
+        assert_eq!(
+            TruthTable::from_ast_with_vars(&Expr::Const(true), &too_wide),
+            Err(FormulaError::TooManyVariables { count: 31, limit: 30 })
+        );
+        assert_eq!(TruthTable::constant(&too_wide, false), Err(FormulaError::TooManyVariables { count: 31, limit: 30 }));
+        assert_eq!(TruthTable::variable(&too_wide, 0), Err(FormulaError::TooManyVariables { count: 31, limit: 30 }));
+        assert_eq!(TruthTable::from_bools(&too_wide, &[]), Err(FormulaError::TooManyVariables { count: 31, limit: 30 }));
+        assert_eq!(TruthTable::from_bools(&vars("AB"), &[true; 3]), Err(FormulaError::RowCount { expected: 4, found: 3 }));
+    }
+
+    #[test]
+    fn test_truth_table_combinators() {
+        let vars = vars("ABCDEFG");
+        let a = TruthTable::variable(&vars, 0).unwrap();
+        let g = TruthTable::variable(&vars, 6).unwrap();
+        let table = |formula: &str| TruthTable::from_ast_with_vars(&parse_expr(formula).unwrap(), &vars).unwrap();
+        assert_eq!(&a & &g, table("AG&"));
+        assert_eq!(&a | &g, table("AG|"));
+        assert_eq!(&a ^ &g, table("AG^"));
+        assert_eq!(!&a, table("A!"));
+        assert_eq!(a.apply(Op::Implies, &g), table("AG>"));
+        assert_eq!(a.apply(Op::Iff, &g), table("AG="));
+        assert_eq!(a.try_apply(Op::And, &g), Ok(table("AG&")));
+        let other = TruthTable::variable(&vars[..2], 0).unwrap();
+        assert_eq!(a.try_apply(Op::And, &other), Err(FormulaError::MismatchedVariables));
+        assert_eq!(TruthTable::variable(&vars, 7), Err(FormulaError::VariableIndex { index: 7, count: 7 }));
+        assert_eq!(TruthTable::variable(&[], 0), Err(FormulaError::VariableIndex { index: 0, count: 0 }));
+
+        // 동치인 식은 같은 진리표
+        assert_eq!(table("AB&!"), table("A!B!|"));
+        assert_eq!(table("AB|C&"), table("AC&BC&|"));
+        assert_ne!(table("AB>"), table("BA>"));
+
+        // 사용하지 않는 비트는 항상 0이므로 부정한 진리표끼리도 비교 가능
+        let small = vars[..2].to_vec();
+        let x = TruthTable::variable(&small, 0).unwrap();
+        assert_eq!(!&!&x, x);
+        assert_eq!((!&TruthTable::constant(&small, false).unwrap()).words(), &[0b1111]);
+        assert!(TruthTable::constant(&small, true).unwrap().is_tautology());
+        assert!((&x ^ &x).is_contradiction());
+        assert_eq!(
+            TruthTable::from_bools(&small, &[false, true, true, false]).unwrap(),
+            &x ^ &TruthTable::variable(&small, 1).unwrap()
+        );
+        assert_eq!(x.to_bools(), vec![false, false, true, true]);
+
+        // 뫼비우스 변환은 자기 역변환. A | B의 ANF 계수: 1인 곳은 A, B, AB
+        let or = TruthTable::from_bools(&small, &[false, true, true, true]).unwrap();
+        assert_eq!(or.mobius().to_string(), "0111");
+        assert_eq!(table("AB&C^DE|&F>G=").mobius().mobius(), table("AB&C^DE|&F>G="));
+    }
+}
@@ -1 +1,4 @@
-pub mod truth_table;
\ No newline at end of file
+pub mod truth_table;
+pub mod bitset;
+pub mod render;
+pub mod synthesis;
\ No newline at end of file
@@ -0,0 +1,415 @@
+use std::fmt::Write;
+use crate::ex02::gray_code::gray_code;
+use crate::ex03::ast::{ast_to_string, expr_to_infix_string, parse_formula, Expr, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use super::bitset::TruthTable;
+use super::truth_table::extract_and_sort_vars_from_ast;
+
+// 진리표를 여러 형식의 문자열로 출력
+// 열은 변수, (선택) 중간 부분식, 결과 순서이고 값은 0/1
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Pipe, // print_truth_table의 형식. 결과 열의 제목은 "="
+    Csv,
+    Json,
+    Markdown,
+    Latex,
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub only_true: bool,     // 결과가 1인 행만 출력
+    pub subformulas: bool,   // 결과 앞에 중간 부분식의 열을 추가
+    pub gray_code: bool,     // 행을 Gray 코드 순서로 출력 (이웃한 행은 변수 하나만 다름)
+    pub notation: Notation,  // 부분식과 결과 열 제목의 표기법
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions { only_true: false, subformulas: false, gray_code: false, notation: Notation::Infix }
+    }
+}
+
+// 출력할 열과 행을 모두 계산한 상태
+struct Layout {
+    vars: Vec<Symbol>,
+    outputs: Vec<(String, TruthTable)>, // 부분식 열과 마지막의 결과 열
+    latex_headers: Vec<String>,         // 출력 열의 LaTeX 제목
+    rows: Vec<usize>,
+}
+
+impl Layout {
+    fn new(ast: &Expr, options: &RenderOptions) -> Result<Layout, FormulaError> {
+        let vars = extract_and_sort_vars_from_ast(ast);
+        let mut columns = Vec::new();
+        if options.subformulas {
+            collect_subformulas(ast, &mut columns);
+            columns.pop(); // 마지막은 식 전체
+        }
+        columns.push(ast);
+
+        let mut outputs = Vec::new();
+        let mut latex_headers = Vec::new();
+        for column in columns {
+            let table = TruthTable::from_ast_with_vars(column, &vars)?;
+            outputs.push((ast_to_string(column, options.notation), table));
+            latex_headers.push(latex_formula(&expr_to_infix_string(column)));
+        }
+        Ok(Layout::with_outputs(vars, outputs, latex_headers, options))
+    }
+
+    fn with_outputs(
+        vars: Vec<Symbol>,
+        outputs: Vec<(String, TruthTable)>,
+        latex_headers: Vec<String>,
+        options: &RenderOptions,
+    ) -> Layout {
+        let result = &outputs.last().expect("the result column").1;
+        let rows = (0..result.len())
+            .map(|i| if options.gray_code { gray_code(i as u32) as usize } else { i })
+            .filter(|&row| !options.only_true || result.get(row))
+            .collect();
+        Layout { vars, outputs, latex_headers, rows }
+    }
+
+    fn headers(&self) -> Vec<String> {
+        self.vars
+            .iter()
+            .map(|var| var.name().to_string())
+            .chain(self.outputs.iter().map(|(header, _)| header.clone()))
+            .collect()
+    }
+
+    // 한 행의 모든 열 값
+    fn values(&self, row: usize) -> Vec<u8> {
+        let n = self.vars.len();
+        (0..n)
+            .map(|j| (row >> (n - 1 - j) & 1) as u8)
+            .chain(self.outputs.iter().map(|(_, table)| table.get(row) as u8))
+            .collect()
+    }
+}
+
+// 부분식을 후위 순서로 모음 (변수와 상수 제외, 같은 부분식은 한 번만)
+fn collect_subformulas<'a>(ast: &'a Expr, subformulas: &mut Vec<&'a Expr>) {
+    match ast {
+        Expr::Const(_) | Expr::Var(_) => return,
+        Expr::Not(operand) => collect_subformulas(operand, subformulas),
+        Expr::Binary(_, left, right) => {
+            collect_subformulas(left, subformulas);
+            collect_subformulas(right, subformulas);
+        }
+    }
+    if !subformulas.contains(&ast) {
+        subformulas.push(ast);
+    }
+}
+
+// 논리식의 진리표를 주어진 형식으로 출력
+pub fn render(ast: &Expr, format: Format, options: &RenderOptions) -> Result<String, FormulaError> {
+    Ok(render_layout(&Layout::new(ast, options)?, format))
+}
+
+// 이미 계산한 진리표를 출력. 결과 열의 제목은 header
+pub fn render_table(table: &TruthTable, header: &str, format: Format, options: &RenderOptions) -> String {
+    let outputs = vec![(header.to_string(), table.clone())];
+    let latex_headers = vec![format!("\\text{{{}}}", latex_text(header))];
+    let layout = Layout::with_outputs(table.vars().to_vec(), outputs, latex_headers, options);
+    render_layout(&layout, format)
+}
+
+/// 주어진 표기법으로 읽어 진리표를 출력
+pub fn try_render_truth_table(
+    formula: &str,
+    notation: Notation,
+    format: Format,
+    options: &RenderOptions,
+) -> Result<String, FormulaError> {
+    render(&parse_formula(formula, notation)?, format, options)
+}
+
+fn render_layout(layout: &Layout, format: Format) -> String {
+    match format {
+        Format::Pipe => render_pipe(layout),
+        Format::Csv => render_csv(layout),
+        Format::Json => render_json(layout),
+        Format::Markdown => render_markdown(layout),
+        Format::Latex => render_latex(layout),
+        Format::Html => render_html(layout),
+    }
+}
+
+// | A | B | = |
+// |---|---|---|
+// | 0 | 1 | 1 |
+// 긴 제목의 열은 값을 가운데 정렬
+fn render_pipe(layout: &Layout) -> String {
+    let mut headers = layout.headers();
+    *headers.last_mut().expect("the result column") = "=".to_string();
+    pipe_table(&headers, layout)
+}
+
+// GitHub Markdown 표. 결과 열의 제목은 식
+fn render_markdown(layout: &Layout) -> String {
+    let headers: Vec<String> = layout.headers().iter().map(|header| header.replace('|', "\\|")).collect();
+    pipe_table(&headers, layout)
+}
+
+fn pipe_table(headers: &[String], layout: &Layout) -> String {
+    let widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    let mut output = String::from("|");
+    for header in headers {
+        let _ = write!(output, " {} |", header);
+    }
+    output.push_str("\n|");
+    for &width in &widths {
+        let _ = write!(output, "{}|", "-".repeat(width + 2));
+    }
+    output.push('\n');
+    for &row in &layout.rows {
+        output.push('|');
+        for (value, &width) in layout.values(row).iter().zip(&widths) {
+            let _ = write!(output, " {:^width$} |", value, width = width);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// 쉼표나 따옴표가 있는 제목은 따옴표로 감쌈
+fn render_csv(layout: &Layout) -> String {
+    let escape = |field: &String| {
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.clone()
+        }
+    };
+    let mut output = layout.headers().iter().map(escape).collect::<Vec<_>>().join(",");
+    output.push('\n');
+    for &row in &layout.rows {
+        let values: Vec<String> = layout.values(row).iter().map(u8::to_string).collect();
+        output.push_str(&values.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+// {"variables":["A","B"],"outputs":["A & B"],"rows":[[0,0,0],...]}
+// 각 행은 변수 값 뒤에 출력 열 값이 이어짐
+fn render_json(layout: &Layout) -> String {
+    let strings = |items: Vec<String>| {
+        let quoted: Vec<String> = items.iter().map(|item| json_string(item)).collect();
+        format!("[{}]", quoted.join(","))
+    };
+    let variables = strings(layout.vars.iter().map(|var| var.name().to_string()).collect());
+    let outputs = strings(layout.outputs.iter().map(|(header, _)| header.clone()).collect());
+    let rows: Vec<String> = layout
+        .rows
+        .iter()
+        .map(|&row| {
+            let values: Vec<String> = layout.values(row).iter().map(u8::to_string).collect();
+            format!("[{}]", values.join(","))
+        })
+        .collect();
+    format!("{{\"variables\":{},\"outputs\":{},\"rows\":[{}]}}\n", variables, outputs, rows.join(","))
+}
+
+fn json_string(text: &str) -> String {
+    let mut output = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+// \begin{tabular}{cc|c} ... \end{tabular}. 변수와 출력 열 사이에 세로선, 제목은 수식 모드의 논리 기호
+fn render_latex(layout: &Layout) -> String {
+    let mut spec = "c".repeat(layout.vars.len());
+    if !layout.vars.is_empty() {
+        spec.push('|');
+    }
+    spec.push_str(&"c".repeat(layout.outputs.len()));
+
+    let headers: Vec<String> = layout
+        .vars
+        .iter()
+        .map(|var| latex_identifier(&var.name()))
+        .chain(layout.latex_headers.iter().cloned())
+        .map(|header| format!("${}$", header))
+        .collect();
+    let mut output = format!("\\begin{{tabular}}{{{}}}\n{} \\\\\n\\hline\n", spec, headers.join(" & "));
+    for &row in &layout.rows {
+        let values: Vec<String> = layout.values(row).iter().map(u8::to_string).collect();
+        let _ = writeln!(output, "{} \\\\", values.join(" & "));
+    }
+    output.push_str("\\end{tabular}\n");
+    output
+}
+
+// 중위 표기 문자열의 연산자를 LaTeX 기호로 바꿈
+fn latex_formula(infix: &str) -> String {
+    let mut output = String::new();
+    let mut chars = infix.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '!' => output.push_str("\\lnot "),
+            '&' => output.push_str("\\land"),
+            '|' => output.push_str("\\lor"),
+            '^' => output.push_str("\\oplus"),
+            '>' => output.push_str("\\rightarrow"),
+            '=' => output.push_str("\\leftrightarrow"),
+            '[' => {
+                let name: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                let _ = write!(output, "\\text{{{}}}", latex_text(&name));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    name.push(next);
+                    chars.next();
+                }
+                output.push_str(&latex_identifier(&name));
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+// 여러 글자 이름은 \mathit로 묶어 글자 사이 간격이 벌어지지 않게 함
+fn latex_identifier(name: &str) -> String {
+    if name.chars().count() == 1 {
+        latex_text(name)
+    } else {
+        format!("\\mathit{{{}}}", latex_text(name))
+    }
+}
+
+fn latex_text(text: &str) -> String {
+    let mut output = String::new();
+    for c in text.chars() {
+        match c {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '\\' => output.push_str("\\textbackslash{}"),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+fn render_html(layout: &Layout) -> String {
+    let mut output = String::from("<table>\n<thead>\n<tr>");
+    for header in layout.headers() {
+        let _ = write!(output, "<th>{}</th>", html_escape(&header));
+    }
+    output.push_str("</tr>\n</thead>\n<tbody>\n");
+    for &row in &layout.rows {
+        output.push_str("<tr>");
+        for value in layout.values(row) {
+            let _ = write!(output, "<td>{}</td>", value);
+        }
+        output.push_str("</tr>\n");
+    }
+    output.push_str("</tbody>\n</table>\n");
+    output
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn postfix(formula: &str, format: Format, options: &RenderOptions) -> String {
+        try_render_truth_table(formula, Notation::Postfix, format, options).unwrap()
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let options = RenderOptions::default();
+        assert_eq!(postfix("AB&", Format::Pipe, &options), "| A | B | = |\n|---|---|---|\n| 0 | 0 | 0 |\n| 0 | 1 | 0 |\n| 1 | 0 | 0 |\n| 1 | 1 | 1 |\n");
+        assert_eq!(postfix("AB&", Format::Csv, &options), "A,B,A & B\n0,0,0\n0,1,0\n1,0,0\n1,1,1\n");
+        assert_eq!(
+            postfix("AB|", Format::Json, &options),
+            "{\"variables\":[\"A\",\"B\"],\"outputs\":[\"A | B\"],\"rows\":[[0,0,0],[0,1,1],[1,0,1],[1,1,1]]}\n"
+        );
+        assert_eq!(
+            postfix("AB|", Format::Markdown, &options),
+            "| A | B | A \\| B |\n|---|---|--------|\n| 0 | 0 |   0    |\n| 0 | 1 |   1    |\n| 1 | 0 |   1    |\n| 1 | 1 |   1    |\n"
+        );
+        assert_eq!(
+            postfix("A!B>", Format::Latex, &options),
+            "\\begin{tabular}{cc|c}\n$A$ & $B$ & $\\lnot A \\rightarrow B$ \\\\\n\\hline\n0 & 0 & 0 \\\\\n0 & 1 & 1 \\\\\n1 & 0 & 1 \\\\\n1 & 1 & 1 \\\\\n\\end{tabular}\n"
+        );
+        assert_eq!(
+            postfix("AB&", Format::Html, &RenderOptions { only_true: true, ..options }),
+            "<table>\n<thead>\n<tr><th>A</th><th>B</th><th>A &amp; B</th></tr>\n</thead>\n<tbody>\n<tr><td>1</td><td>1</td><td>1</td></tr>\n</tbody>\n</table>\n"
+        );
+
+        // 이름과 식의 특수 문자
+        let csv = RenderOptions { notation: Notation::Postfix, ..options };
+        assert_eq!(postfix("[a,b]", Format::Csv, &csv).lines().next(), Some("\"a,b\",\"[a,b]\""));
+        let latex = postfix("[x_1][big_door]^", Format::Latex, &options);
+        assert!(latex.contains("$\\mathit{big\\_door}$ & $\\mathit{x\\_1}$ & $\\mathit{x\\_1} \\oplus \\mathit{big\\_door}$"), "{}", latex);
+        assert_eq!(
+            try_render_truth_table("\"q\"", Notation::Infix, Format::Json, &options),
+            Err(FormulaError::UnexpectedCharacter { ch: '"', offset: 0 })
+        );
+        assert_eq!(postfix("1", Format::Json, &options), "{\"variables\":[],\"outputs\":[\"1\"],\"rows\":[[1]]}\n");
+        assert_eq!(postfix("1", Format::Latex, &options), "\\begin{tabular}{c}\n$1$ \\\\\n\\hline\n1 \\\\\n\\end{tabular}\n");
+    }
+
+    #[test]
+    fn test_render_options() {
+        // 중간 부분식 열 (같은 부분식은 한 번만)
+        let options = RenderOptions { subformulas: true, ..RenderOptions::default() };
+        assert_eq!(
+            postfix("AB!&AB!&|", Format::Csv, &options),
+            "A,B,!B,A & !B,A & !B | A & !B\n0,0,1,0,0\n0,1,0,0,0\n1,0,1,1,1\n1,1,0,0,0\n"
+        );
+        assert_eq!(
+            postfix("AB!&", Format::Pipe, &options),
+            "| A | B | !B | = |\n|---|---|----|---|\n| 0 | 0 | 1  | 0 |\n| 0 | 1 | 0  | 0 |\n| 1 | 0 | 1  | 1 |\n| 1 | 1 | 0  | 0 |\n"
+        );
+
+        // Gray 코드 순서: 이웃한 행은 변수 하나만 다름
+        let gray = RenderOptions { gray_code: true, ..RenderOptions::default() };
+        assert_eq!(postfix("AB^", Format::Csv, &gray), "A,B,A ^ B\n0,0,0\n0,1,1\n1,1,0\n1,0,1\n");
+        let csv = postfix("ABC||", Format::Csv, &gray);
+        let rows: Vec<u32> = csv.lines().skip(1).map(|line| u32::from_str_radix(&line[..5].replace(',', ""), 2).unwrap()).collect();
+        assert!(rows.windows(2).all(|pair| (pair[0] ^ pair[1]).count_ones() == 1));
+
+        // 결과가 1인 행만, Gray 코드 순서와 함께
+        let both = RenderOptions { only_true: true, gray_code: true, ..RenderOptions::default() };
+        assert_eq!(postfix("AB|", Format::Csv, &both), "A,B,A | B\n0,1,1\n1,1,1\n1,0,1\n");
+        assert_eq!(postfix("AA!&", Format::Csv, &both), "A,A & !A\n");
+
+        // 이미 계산한 진리표
+        let table = TruthTable::from_bools(&[Symbol::from('A')], &[true, false]).unwrap();
+        assert_eq!(render_table(&table, "f", Format::Csv, &RenderOptions::default()), "A,f\n0,1\n1,0\n");
+        assert!(render_table(&table, "f_1", Format::Latex, &RenderOptions::default()).contains("$\\text{f\\_1}$"));
+    }
+}
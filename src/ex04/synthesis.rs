@@ -0,0 +1,372 @@
+use std::fmt;
+use crate::ex03::ast::{ast_to_string, Expr, Notation, Op};
+use std::collections::HashSet;
+use crate::ex03::symbol::{is_identifier, Symbol, MAX_INTERNED_BYTES};
+use crate::ex06::disjunctive_normal_form::maxterm;
+use crate::minimize::cube::{cover_to_expr, Cube};
+use crate::minimize::espresso::espresso;
+use crate::minimize::quine_mccluskey::{minimize_table, MAX_EXACT_VARIABLES};
+use super::bitset::{TruthTable, MAX_TABLE_VARIABLES};
+
+// 진리표에서 논리식을 합성 (generate_truth_table의 역방향)
+//
+// | A | B | = |        A,B,f
+// |---|---|---|        0,0,0
+// | 0 | 0 | 0 |        0,1,1
+// | 0 | 1 | 1 |        ...
+//
+// print_truth_table의 형식과 CSV를 읽는다. 마지막 열이 결과이고 나머지는 변수 열
+// 행 순서는 자유이며, 결과 칸의 x 또는 -는 don't care
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TableError {
+    Empty,
+    InvalidHeader { line: usize },
+    DuplicateVariable { line: usize, name: String },
+    ColumnCount { line: usize, expected: usize, found: usize },
+    InvalidValue { line: usize, value: String },
+    DuplicateRow { line: usize },
+    MissingRows { count: usize },
+    TooManyVariables { count: usize, limit: usize },
+    TooManySymbols { limit: usize },
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::Empty => write!(f, "empty truth table"),
+            TableError::InvalidHeader { line } => write!(f, "invalid header at line {}", line),
+            TableError::DuplicateVariable { line, name } => {
+                write!(f, "variable '{}' appears twice in the header at line {}", name, line)
+            }
+            TableError::ColumnCount { line, expected, found } => {
+                write!(f, "expected {} columns at line {} but found {}", expected, line, found)
+            }
+            TableError::InvalidValue { line, value } => write!(f, "invalid value '{}' at line {}", value, line),
+            TableError::DuplicateRow { line } => write!(f, "row at line {} is already defined", line),
+            TableError::MissingRows { count } => write!(f, "{} rows are missing", count),
+            TableError::TooManyVariables { count, limit } => {
+                write!(f, "{} variables exceed the limit of {}", count, limit)
+            }
+            TableError::TooManySymbols { limit } => write!(f, "variable names exceed the limit of {} bytes", limit),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+// 읽어 들인 진리표. on은 결과가 1인 행, dont_care는 값이 상관없는 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSpec {
+    pub on: TruthTable,
+    pub dont_care: TruthTable,
+}
+
+impl TableSpec {
+    pub fn vars(&self) -> &[Symbol] {
+        self.on.vars()
+    }
+
+    fn rows(&self, table: &TruthTable) -> Vec<u64> {
+        (0..table.len()).filter(|&row| table.get(row)).map(|row| row as u64).collect()
+    }
+}
+
+// 합성할 식의 모양
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SynthesisForm {
+    SumOfMinterms,     // 정규 DNF
+    ProductOfMaxterms, // 정규 CNF
+    Minimized,         // 최소 곱의 합. 변수가 많으면 Espresso
+}
+
+// 첫 줄이 '|'로 시작하면 파이프 형식, 아니면 CSV로 읽음
+pub fn parse_table(input: &str) -> Result<TableSpec, TableError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let (header_line, header) = lines.next().ok_or(TableError::Empty)?;
+
+    let pipe = header.starts_with('|');
+    let split = |line: &str| -> Option<Vec<String>> {
+        if pipe {
+            let inner = line.strip_prefix('|')?.strip_suffix('|')?;
+            Some(inner.split('|').map(|cell| cell.trim().to_string()).collect())
+        } else {
+            split_csv(line)
+        }
+    };
+
+    let columns = split(header).ok_or(TableError::InvalidHeader { line: header_line })?;
+    if columns.iter().any(|column| column.is_empty()) {
+        return Err(TableError::InvalidHeader { line: header_line });
+    }
+    // 변수 열의 제목은 중위 표기의 식별자여야 하고 중복될 수 없음. 결과 열은 자유
+    let names = &columns[..columns.len() - 1];
+    let mut seen_names = HashSet::new();
+    for name in names {
+        if !is_identifier(name) {
+            return Err(TableError::InvalidHeader { line: header_line });
+        }
+        if !seen_names.insert(name.as_str()) {
+            return Err(TableError::DuplicateVariable { line: header_line, name: name.clone() });
+        }
+    }
+    let vars = names
+        .iter()
+        .map(|name| Symbol::try_intern(name))
+        .collect::<Result<Vec<Symbol>, _>>()
+        .map_err(|_| TableError::TooManySymbols { limit: MAX_INTERNED_BYTES })?;
+    let empty = TruthTable::constant(&vars, false)
+        .map_err(|_| TableError::TooManyVariables { count: vars.len(), limit: MAX_TABLE_VARIABLES })?;
+    let mut on = empty.clone();
+    let mut dont_care = empty.clone();
+    let mut seen = empty;
+    for (line_number, line) in lines {
+        let cells = split(line).ok_or(TableError::InvalidValue { line: line_number, value: line.to_string() })?;
+        // 파이프 형식의 구분선 |---|---|
+        if pipe && cells.iter().all(|cell| !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':')) {
+            continue;
+        }
+        if cells.len() != columns.len() {
+            return Err(TableError::ColumnCount { line: line_number, expected: columns.len(), found: cells.len() });
+        }
+        let invalid = |cell: &String| TableError::InvalidValue { line: line_number, value: cell.clone() };
+        let mut row = 0;
+        for cell in &cells[..vars.len()] {
+            let bit = match cell.as_str() {
+                "0" => 0,
+                "1" => 1,
+                _ => return Err(invalid(cell)),
+            };
+            row = row << 1 | bit;
+        }
+        if seen.get(row) {
+            return Err(TableError::DuplicateRow { line: line_number });
+        }
+        seen.set(row, true);
+        match cells[vars.len()].as_str() {
+            "0" => {}
+            "1" => on.set(row, true),
+            "x" | "X" | "-" => dont_care.set(row, true),
+            _ => return Err(invalid(&cells[vars.len()])),
+        }
+    }
+
+    let missing = seen.len() - seen.count_ones() as usize;
+    if missing > 0 {
+        return Err(TableError::MissingRows { count: missing });
+    }
+    Ok(TableSpec { on, dont_care })
+}
+
+// render_csv가 쓰는 형식의 한 줄을 칸으로 나눔. "..." 안의 ,는 구분자가 아니고 ""는 "
+// 따옴표가 닫히지 않았거나 닫는 따옴표 뒤에 다른 글자가 있으면 None
+fn split_csv(line: &str) -> Option<Vec<String>> {
+    let mut cells = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|&c| c == ' ' || c == '\t').is_some() {}
+        let mut cell = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next()? {
+                    '"' if chars.next_if_eq(&'"').is_some() => cell.push('"'),
+                    '"' => break,
+                    c => cell.push(c),
+                }
+            }
+            while chars.next_if(|&c| c == ' ' || c == '\t').is_some() {}
+            if chars.peek().is_some_and(|&c| c != ',') {
+                return None;
+            }
+        } else {
+            while let Some(c) = chars.next_if(|&c| c != ',') {
+                cell.push(c);
+            }
+            cell = cell.trim().to_string();
+        }
+        cells.push(cell);
+        if chars.next().is_none() {
+            return Some(cells);
+        }
+    }
+}
+
+// 진리표를 만족하는 식. 정규형에서 don't care 행은 0으로 취급
+// 결과는 Expr이며, 한 글자 변수만 있으면 ASTNode::try_from으로 예전 AST로 바꿀 수 있음
+// 상수 함수도 모든 변수가 나타나는 식으로 만들어 다시 진리표를 만들면 같은 열이 나옴
+pub fn synthesize(spec: &TableSpec, form: SynthesisForm) -> Expr {
+    let vars = spec.vars();
+    match synthesize_expr(spec, form) {
+        Expr::Const(value) => full_width_constant(vars, value),
+        expr => expr,
+    }
+}
+
+fn synthesize_expr(spec: &TableSpec, form: SynthesisForm) -> Expr {
+    let vars = spec.vars();
+    let n = vars.len();
+    match form {
+        SynthesisForm::SumOfMinterms => {
+            let minterms: Vec<Cube> = spec.rows(&spec.on).iter().map(|&row| Cube::minterm(row, n)).collect();
+            cover_to_expr(&minterms, vars)
+        }
+        SynthesisForm::ProductOfMaxterms => {
+            // 0인 행의 최소항을 부정한 것이 최대항: !(A & !B) -> !A | B
+            let off = &!&spec.on;
+            let mut maxterms = spec.rows(off).into_iter().map(|row| maxterm(vars, row as usize));
+            match maxterms.next() {
+                Some(first) => maxterms.fold(first, Expr::and),
+                None => Expr::Const(true),
+            }
+        }
+        SynthesisForm::Minimized => {
+            let minterms = spec.rows(&spec.on);
+            let dont_cares = spec.rows(&spec.dont_care);
+            if n <= MAX_EXACT_VARIABLES {
+                minimize_table(vars, &minterms, &dont_cares).expect("variable count is within the exact limit")
+            } else {
+                let cubes = |rows: &[u64]| rows.iter().map(|&row| Cube::minterm(row, n)).collect::<Vec<_>>();
+                cover_to_expr(&espresso(&cubes(&minterms), &cubes(&dont_cares)), vars)
+            }
+        }
+    }
+}
+
+// 모든 변수가 나타나는 상수. 거짓은 A & !A & B & !B ..., 참은 A | !A | B | !B ...
+fn full_width_constant(vars: &[Symbol], value: bool) -> Expr {
+    let op = if value { Op::Or } else { Op::And };
+    vars.iter()
+        .map(|&var| Expr::binary(op, Expr::Var(var), Expr::negate(Expr::Var(var))))
+        .reduce(|acc, next| Expr::binary(op, acc, next))
+        .unwrap_or(Expr::Const(value))
+}
+
+pub fn try_synthesize(input: &str, form: SynthesisForm) -> Result<Expr, TableError> {
+    Ok(synthesize(&parse_table(input)?, form))
+}
+
+/// 진리표를 읽어 합성한 식을 주어진 표기법으로 반환
+pub fn try_synthesize_formula(input: &str, form: SynthesisForm, notation: Notation) -> Result<String, TableError> {
+    Ok(ast_to_string(&try_synthesize(input, form)?, notation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::{parse_expr, ASTNode};
+    use crate::ex04::render::{render, render_table, Format, RenderOptions};
+    use crate::ex04::truth_table::generate_truth_table;
+
+    #[test]
+    fn test_parse_table() {
+        let xor = "| A | B | = |\n|---|---|---|\n| 0 | 0 | 0 |\n| 0 | 1 | 1 |\n| 1 | 0 | 1 |\n| 1 | 1 | 0 |\n";
+        let spec = parse_table(xor).unwrap();
+        assert_eq!(spec.on, TruthTable::from_ast(&parse_expr("AB^").unwrap()).unwrap());
+        assert!(spec.dont_care.is_contradiction());
+
+        // CSV, 임의의 행 순서, don't care
+        let spec = parse_table("door,x1,f\n1,1,x\n0,0,0\n1,0,1\n0,1,-\n").unwrap();
+        assert_eq!(spec.vars(), &[Symbol::intern("door"), Symbol::intern("x1")]);
+        assert_eq!(spec.on.to_string(), "0010");
+        assert_eq!(spec.dont_care.to_string(), "0101");
+
+        assert_eq!(parse_table(" \n"), Err(TableError::Empty));
+        assert_eq!(parse_table("A,,f\n"), Err(TableError::InvalidHeader { line: 1 }));
+        assert_eq!(
+            parse_table("A,A,f\n"),
+            Err(TableError::DuplicateVariable { line: 1, name: "A".to_string() })
+        );
+        assert_eq!(parse_table("\na b,f\n"), Err(TableError::InvalidHeader { line: 2 }));
+        assert_eq!(parse_table("| ( | = |\n"), Err(TableError::InvalidHeader { line: 1 }));
+        assert_eq!(parse_table("1x,f\n"), Err(TableError::InvalidHeader { line: 1 }));
+        assert!(parse_table("_a,A & B\n0,0\n1,1\n").is_ok());
+        assert_eq!(parse_table("A,\"f\n"), Err(TableError::InvalidHeader { line: 1 }));
+        assert_eq!(parse_table("\"A\"x,f\n"), Err(TableError::InvalidHeader { line: 1 }));
+        assert_eq!(parse_table("A,f\n0,1\n0,0\n"), Err(TableError::DuplicateRow { line: 3 }));
+        assert_eq!(parse_table("A,f\n0,1\n"), Err(TableError::MissingRows { count: 1 }));
+        assert_eq!(
+            parse_table("A,f\n0,1,1\n"),
+            Err(TableError::ColumnCount { line: 2, expected: 2, found: 3 })
+        );
+        assert_eq!(
+            parse_table("| A | = |\n| 0 | 2 |\n"),
+            Err(TableError::InvalidValue { line: 2, value: "2".to_string() })
+        );
+        assert_eq!(TableError::MissingRows { count: 3 }.to_string(), "3 rows are missing");
+    }
+
+    #[test]
+    fn test_synthesize() {
+        let table = "A,B,C,f\n0,0,0,0\n0,0,1,1\n0,1,0,0\n0,1,1,1\n1,0,0,0\n1,0,1,1\n1,1,0,1\n1,1,1,1\n";
+        let postfix = |form| try_synthesize_formula(table, form, Notation::Postfix).unwrap();
+        assert_eq!(postfix(SynthesisForm::SumOfMinterms), "A!B!&C&A!B&C&|AB!&C&|AB&C!&|AB&C&|");
+        assert_eq!(postfix(SynthesisForm::ProductOfMaxterms), "AB|C|AB!|C|&A!B|C|&");
+        assert_eq!(postfix(SynthesisForm::Minimized), "CAB&|");
+
+        // don't care는 최소화에서만 사용
+        let spec = parse_table("A,B,f\n0,0,0\n0,1,0\n1,0,x\n1,1,1\n").unwrap();
+        assert_eq!(synthesize(&spec, SynthesisForm::Minimized), parse_expr("A").unwrap());
+        assert_eq!(synthesize(&spec, SynthesisForm::SumOfMinterms), parse_expr("AB&").unwrap());
+
+        // 상수 함수도 모든 변수가 나타남
+        let spec = parse_table("A,f\n0,0\n1,0\n").unwrap();
+        assert_eq!(synthesize(&spec, SynthesisForm::SumOfMinterms), parse_expr("AA!&").unwrap());
+        assert_eq!(synthesize(&spec, SynthesisForm::ProductOfMaxterms), parse_expr("AA!&").unwrap());
+        assert_eq!(synthesize(&spec, SynthesisForm::Minimized), parse_expr("AA!&").unwrap());
+        let spec = parse_table("A,B,f\n0,0,1\n0,1,1\n1,0,1\n1,1,1\n").unwrap();
+        assert_eq!(synthesize(&spec, SynthesisForm::ProductOfMaxterms), parse_expr("AA!|BB!||").unwrap());
+        assert_eq!(synthesize(&spec, SynthesisForm::Minimized), parse_expr("AA!|BB!||").unwrap());
+        assert_eq!(try_synthesize("f\n1\n", SynthesisForm::SumOfMinterms), Ok(Expr::Const(true)));
+
+        // 예전 AST로 변환
+        let expr = try_synthesize(table, SynthesisForm::Minimized).unwrap();
+        assert_eq!(ASTNode::try_from(&expr).unwrap().to_string(), "CAB&|");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // print_truth_table의 출력을 읽어 합성한 식은 같은 진리표를 만듦
+        for formula in ["AB&C|", "AB^", "AB>C>", "ABC==", "AB=C!&D|", "[x1][door]|A!&", "AA!&", "AA!|", "AB^AB=&C&", "AB>BA>|C|"] {
+            let printed = generate_truth_table(formula).unwrap();
+            for form in [SynthesisForm::SumOfMinterms, SynthesisForm::ProductOfMaxterms] {
+                let synthesized = try_synthesize(&printed, form).unwrap();
+                assert_eq!(generate_truth_table(&synthesized.to_string()).unwrap(), printed, "{} {:?}", formula, form);
+            }
+            let minimized = try_synthesize(&printed, SynthesisForm::Minimized).unwrap();
+            let vars = parse_table(&printed).unwrap().vars().to_vec();
+            assert_eq!(
+                TruthTable::from_ast_with_vars(&minimized, &vars).unwrap(),
+                TruthTable::from_ast(&parse_expr(formula).unwrap()).unwrap()
+            );
+
+            // CSV와 Gray 코드 순서의 출력도 읽을 수 있음
+            let options = RenderOptions { gray_code: true, ..RenderOptions::default() };
+            let csv = render(&parse_expr(formula).unwrap(), Format::Csv, &options).unwrap();
+            assert_eq!(parse_table(&csv).unwrap(), parse_table(&printed).unwrap(), "{}", formula);
+        }
+
+        // 쉼표나 따옴표가 든 결과 열 제목은 render_csv가 따옴표로 감싸고 parse_table이 되읽음
+        let table = TruthTable::from_ast(&parse_expr("AB|").unwrap()).unwrap();
+        for header in ["f, g", "say \"hi\"", " , "] {
+            let csv = render_table(&table, header, Format::Csv, &RenderOptions::default());
+            assert!(csv.starts_with("A,B,\""), "{}", csv);
+            let spec = parse_table(&csv).unwrap();
+            assert_eq!(spec.on, table);
+            assert!(spec.dont_care.is_contradiction());
+        }
+        assert_eq!(
+            split_csv(" a , \"b,\"\"c\"\"\" ,"),
+            Some(vec!["a".to_string(), "b,\"c\"".to_string(), String::new()])
+        );
+
+        // 17개 변수 이상은 Espresso로 최소화: 패리티가 아닌 a0 & a16
+        let vars: Vec<Symbol> = (0..17).map(|i| Symbol::intern(&format!("a{}", i))).collect();
+        let ast = Expr::and(Expr::Var(vars[0]), Expr::Var(vars[16]));
+        let csv = render(&ast, Format::Csv, &RenderOptions::default()).unwrap();
+        assert_eq!(try_synthesize(&csv, SynthesisForm::Minimized), Ok(ast));
+    }
+}
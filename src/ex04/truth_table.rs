@@ -1,114 +1,71 @@
-use std::collections::HashMap;
-use anyhow::{Result, Context};
-use crate::ex03::ast::{get_ast, ASTNode};
-use crate::ex03::boolean_evaluation::calculate;
+use crate::ex03::ast::{Expr, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
 use std::collections::HashSet;
+use super::bitset::TruthTable;
+use super::render::{try_render_truth_table, Format, RenderOptions};
 
 /// 모든 변수 조합에 대해 수식을 평가하여 진리표를 생성하는 함수
-fn generate_truth_table(formula: &str) -> Result<String> {
-    let ast = get_ast(formula).context("Failed to create AST from formula")?;
-    let vars = extract_and_sort_vars_from_ast(&ast);
-    let mut output = create_truth_table_header(&vars);
-    output.push_str(&evaluate_combinations(&ast, &vars)?);
-    Ok(output)
+pub fn generate_truth_table(formula: &str) -> Result<String, FormulaError> {
+    generate_truth_table_with_notation(formula, Notation::Postfix)
+}
+
+pub fn generate_truth_table_with_notation(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    try_render_truth_table(formula, notation, Format::Pipe, &RenderOptions::default())
 }
 
 
-fn extract_and_sort_vars_from_ast(ast: &ASTNode) -> Vec<char> {
+pub fn extract_and_sort_vars_from_ast(ast: &Expr) -> Vec<Symbol> {
     let mut vars = HashSet::new();
     extract_variables(ast, &mut vars);
     
-    let mut vars: Vec<char> = vars.into_iter().collect();
+    let mut vars: Vec<Symbol> = vars.into_iter().collect();
     vars.sort();
     vars
 }
 
 /// 재귀적으로 AST를 순회하며 변수를 추출하는 헬퍼 함수
-pub fn extract_variables(ast: &ASTNode, variables: &mut HashSet<char>) {
+pub fn extract_variables(ast: &Expr, variables: &mut HashSet<Symbol>) {
     match ast {
-        ASTNode::Operand(var) => {
+        Expr::Const(_) => {}
+        Expr::Var(var) => {
             variables.insert(*var);
         }
-        ASTNode::Operator(_, left, Some(right)) => {
+        Expr::Not(operand) => extract_variables(operand, variables),
+        Expr::Binary(_, left, right) => {
             extract_variables(left, variables);
             extract_variables(right, variables);
         }
-        ASTNode::Operator(_, operand, None) => {
-            extract_variables(operand, variables);
-        }
     }
 }
 
-/// 진리표의 헤더와 구분선을 생성하는 함수
-fn create_truth_table_header(vars: &[char]) -> String {
-    let mut header = String::new();
-    header.push('|');
-    for &var in vars {
-        header.push_str(&format!(" {} |", var));
-    }
-    header.push_str(" = |\n|");
-    header.push_str(&format!("{}\n", "---|".repeat(vars.len() + 1)));
-    header
+/// 진리표의 결과 열만 계산하는 함수
+/// i번째 값은 Assignment::from_row(vars, i)로 평가한 결과 (첫 번째 변수가 최상위 비트)
+pub fn evaluate_truth_table(ast: &Expr, vars: &[Symbol]) -> Result<Vec<bool>, FormulaError> {
+    Ok(TruthTable::from_ast_with_vars(ast, vars)?.to_bools())
 }
 
+/// 진리표를 출력하는 함수
+pub fn print_truth_table(formula: &str) {
+    print_truth_table_with_notation(formula, Notation::Postfix);
+}
 
-fn evaluate_combinations(ast: &ASTNode, vars: &[char]) -> Result<String> {
-    let mut output = String::new();
-    let num_vars = vars.len();
-
-    for i in 0..(1 << num_vars) {
-        output.push('|');
-        let mut eval_map = HashMap::new();
-        
-        // 각 변수에 대해 현재 조합에 해당하는 값을 할당
-        for (j, &var) in vars.iter().enumerate() {
-            let value = (i >> (num_vars - j - 1)) & 1 == 1;
-            eval_map.insert(var, value);
-            output.push_str(&format!(" {} |", if value { 1 } else { 0 }));
-        }
-
-        // AST를 사용하여 수식 평가
-        let result = evaluate_ast(ast, &eval_map);
-        output.push_str(&format!(" {} |\n", if result { 1 } else { 0 }));
+/// 주어진 표기법으로 작성된 수식의 진리표를 출력하는 함수
+pub fn print_truth_table_with_notation(formula: &str, notation: Notation) {
+    if let Err(e) = try_print_truth_table_with_notation(formula, notation) {
+        eprintln!("Error generating truth table: {}", e);
     }
-
-    Ok(output)
 }
 
-fn evaluate_ast(node: &ASTNode, var_map: &HashMap<char, bool>) -> bool {
-    match node {
-        ASTNode::Operand(c) => {
-            match c {
-                '0' => false,  // '0'은 false를 나타냄
-                '1' => true,   // '1'은 true를 나타냄
-                _ if c.is_alphabetic() => *var_map.get(c).unwrap_or_else(|| panic!("Variable {} not found in map", c)),
-                _ => panic!("Unexpected operand: {}", c),  // 예상하지 못한 피연산자는 패닉 발생
-            }
-        }
-        ASTNode::Operator('!', left, _) => {
-            let val = evaluate_ast(left, var_map);
-            !val
-        }
-        ASTNode::Operator(op, left, right_opt) => {
-            let left_val = evaluate_ast(left, var_map);
-
-            // `right`가 `Some`이면 그 값을 평가하고, `None`이면 기본값으로 처리
-            let right_val = match right_opt {
-                Some(right) => evaluate_ast(right, var_map), // `Some`이면 평가
-                None => false, // 단항 연산자는 여기서 처리되지 않으므로 기본값은 사용되지 않음
-            };
-
-            calculate(*op, left_val, right_val)
-        }
-    }
+/// 진리표를 출력하고, 실패하면 에러를 반환하는 함수
+pub fn try_print_truth_table(formula: &str) -> Result<(), FormulaError> {
+    print!("{}", generate_truth_table(formula)?);
+    Ok(())
 }
 
-/// 진리표를 출력하는 함수
-pub fn print_truth_table(formula: &str) {
-    match generate_truth_table(formula) {
-        Ok(output) => print!("{}", output),
-        Err(e) => eprintln!("Error generating truth table: {}", e),
-    }
+pub fn try_print_truth_table_with_notation(formula: &str, notation: Notation) -> Result<(), FormulaError> {
+    print!("{}", generate_truth_table_with_notation(formula, notation)?);
+    Ok(())
 }
 
 
@@ -206,3 +163,40 @@ fn test_truth_table() {
         "| A | B | = |\n|---|---|---|\n| 0 | 0 | 1 |\n| 0 | 1 | 1 |\n| 1 | 0 | 1 |\n| 1 | 1 | 1 |\n"
     );
 }
+
+#[test]
+fn test_truth_table_infix() {
+    let res = generate_truth_table_with_notation("A & B | C", Notation::Infix).unwrap();
+    assert_eq!(res, generate_truth_table("AB&C|").unwrap());
+
+    let res = generate_truth_table_with_notation("A → (B ↔ C)", Notation::Infix).unwrap();
+    assert_eq!(res, generate_truth_table("ABC=>").unwrap());
+
+    assert!(generate_truth_table_with_notation("A & (B", Notation::Infix).is_err());
+}
+
+#[test]
+fn test_truth_table_errors() {
+    assert_eq!(generate_truth_table("AB&C|&"), Err(FormulaError::StackUnderflow { token: 5, operator: '&' }));
+    assert_eq!(generate_truth_table("AB"), Err(FormulaError::LeftoverOperands { count: 2 }));
+}
+
+#[test]
+fn test_truth_table_constants() {
+    // 상수는 변수 열로 취급하지 않음
+    let res = generate_truth_table("A1&").unwrap();
+    assert_eq!(res, "| A | = |\n|---|---|\n| 0 | 0 |\n| 1 | 1 |\n");
+}
+
+#[test]
+fn test_truth_table_named_variables() {
+    let res = generate_truth_table("[x1][door]|").unwrap();
+    assert_eq!(
+        res,
+        "| door | x1 | = |\n|------|----|---|\n|  0   | 0  | 0 |\n|  0   | 1  | 1 |\n|  1   | 0  | 1 |\n|  1   | 1  | 1 |\n"
+    );
+    assert_eq!(
+        generate_truth_table_with_notation("x1 | door", Notation::Infix).unwrap(),
+        res
+    );
+}
@@ -1,58 +1,142 @@
-use crate::ex03::ast::{ASTNode, get_ast, ast_to_postfix_string};
+use crate::ex03::ast::{Expr, Op, parse_expr, expr_to_postfix_string};
+use crate::ex03::error::FormulaError;
 
 pub fn negation_normal_form(formula: &str) -> String {
-    let ast = get_ast(formula).expect("Failed to parse formula");
+    try_negation_normal_form(formula).unwrap_or_else(|e| panic!("Failed to parse formula: {}", e))
+}
+
+pub fn try_negation_normal_form(formula: &str) -> Result<String, FormulaError> {
+    let ast = parse_expr(formula)?;
     let nnf_ast = nnf(&ast);
-    ast_to_postfix_string(&nnf_ast)
+    Ok(expr_to_postfix_string(&nnf_ast))
 }
 
-fn apply_de_morgan(left: &ASTNode) -> ASTNode {
+// Rewrites '>', '=' and '^' using only '!', '&' and '|'
+pub fn eliminate_derived_operators(ast: &Expr) -> Expr {
+    match ast {
+        Expr::Const(_) | Expr::Var(_) => ast.clone(),
+        Expr::Not(operand) => Expr::negate(eliminate_derived_operators(operand)),
+        Expr::Binary(op, left, right) => {
+            expand_operator(*op, eliminate_derived_operators(left), eliminate_derived_operators(right))
+        }
+    }
+}
+
+fn expand_operator(op: Op, left: Expr, right: Expr) -> Expr {
+    match op {
+        Op::And | Op::Or => Expr::binary(op, left, right),
+        // A > B -> !A | B
+        Op::Implies => Expr::or(Expr::negate(left), right),
+        // A = B -> (A & B) | (!A & !B)
+        Op::Iff => Expr::or(
+            Expr::and(left.clone(), right.clone()),
+            Expr::and(Expr::negate(left), Expr::negate(right)),
+        ),
+        // A ^ B -> (A & !B) | (!A & B)
+        Op::Xor => Expr::or(
+            Expr::and(left.clone(), Expr::negate(right.clone())),
+            Expr::and(Expr::negate(left), right),
+        ),
+    }
+}
+
+// Pushes a negation one level down: !X is rewritten according to the top node of X
+fn apply_de_morgan(left: &Expr) -> Expr {
     match left {
+        // Negating a constant: !0 -> 1, !1 -> 0
+        Expr::Const(value) => Expr::Const(!value),
+
+        // A negated variable is already in NNF
+        Expr::Var(_) => Expr::negate(left.clone()),
+
         // Negating a negation: !!A -> A
-        ASTNode::Operator('!', inner, _) => nnf(inner),
+        Expr::Not(inner) => nnf(inner),
 
         // Negating a conjunction: !(A & B) -> !A | !B
-        ASTNode::Operator('&', left_inner, Some(right_inner)) => {
-            let neg_left = nnf(&ASTNode::Operator('!', Box::new(nnf(left_inner)), None));
-            let neg_right = nnf(&ASTNode::Operator('!', Box::new(nnf(right_inner)), None));
-            ASTNode::Operator('|', Box::new(neg_left), Some(Box::new(neg_right)))
+        Expr::Binary(Op::And, left_inner, right_inner) => {
+            Expr::or(apply_de_morgan(left_inner), apply_de_morgan(right_inner))
         }
 
         // Negating a disjunction: !(A | B) -> !A & !B
-        ASTNode::Operator('|', left_inner, Some(right_inner)) => {
-            let neg_left = nnf(&ASTNode::Operator('!', Box::new(nnf(left_inner)), None));
-            let neg_right = nnf(&ASTNode::Operator('!', Box::new(nnf(right_inner)), None));
-            ASTNode::Operator('&', Box::new(neg_left), Some(Box::new(neg_right)))
+        Expr::Binary(Op::Or, left_inner, right_inner) => {
+            Expr::and(apply_de_morgan(left_inner), apply_de_morgan(right_inner))
+        }
+
+        // Negating an implication: !(A > B) -> A & !B
+        Expr::Binary(Op::Implies, left_inner, right_inner) => {
+            Expr::and(nnf(left_inner), apply_de_morgan(right_inner))
         }
 
-        // Negation of a simple operand or an unsupported case
-        _ => ASTNode::Operator('!', Box::new(nnf(left)), None),
+        // Negating an equivalence: !(A = B) -> (!A | !B) & (A | B)
+        Expr::Binary(Op::Iff, left_inner, right_inner) => Expr::and(
+            Expr::or(apply_de_morgan(left_inner), apply_de_morgan(right_inner)),
+            Expr::or(nnf(left_inner), nnf(right_inner)),
+        ),
+
+        // Negating an exclusive or: !(A ^ B) -> (!A | B) & (A | !B)
+        Expr::Binary(Op::Xor, left_inner, right_inner) => Expr::and(
+            Expr::or(apply_de_morgan(left_inner), nnf(right_inner)),
+            Expr::or(nnf(left_inner), apply_de_morgan(right_inner)),
+        ),
     }
 }
 
-pub fn nnf(ast: &ASTNode) -> ASTNode {
+// Converts to NNF: negations end up directly on variables and '>', '=', '^' are expanded
+pub fn nnf(ast: &Expr) -> Expr {
     match ast {
         // Operands are returned as-is
-        ASTNode::Operand(_) => ast.clone(),
+        Expr::Const(_) | Expr::Var(_) => ast.clone(),
 
         // Negation is handled via apply_de_morgan
-        ASTNode::Operator('!', left, _) => apply_de_morgan(left),
+        Expr::Not(operand) => apply_de_morgan(operand),
 
-        // For other operators, we apply NNF recursively to both operands
-        ASTNode::Operator(op, left, Some(right)) => {
-            let left_nnf = nnf(left);
-            let right_nnf = nnf(right);
-            ASTNode::Operator(*op, Box::new(left_nnf), Some(Box::new(right_nnf)))
-        }
+        // A > B -> !A | B
+        Expr::Binary(Op::Implies, left, right) => Expr::or(apply_de_morgan(left), nnf(right)),
+
+        // A = B -> (A & B) | (!A & !B)
+        Expr::Binary(Op::Iff, left, right) => Expr::or(
+            Expr::and(nnf(left), nnf(right)),
+            Expr::and(apply_de_morgan(left), apply_de_morgan(right)),
+        ),
 
-        // Malformed AST or unsupported cases
-        _ => ast.clone(),
+        // A ^ B -> (A & !B) | (!A & B)
+        Expr::Binary(Op::Xor, left, right) => Expr::or(
+            Expr::and(nnf(left), apply_de_morgan(right)),
+            Expr::and(apply_de_morgan(left), nnf(right)),
+        ),
+
+        // For '&' and '|', we apply NNF recursively to both operands
+        Expr::Binary(op, left, right) => Expr::binary(*op, nnf(left), nnf(right)),
+    }
+}
+
+// Checks that the expression only uses '&', '|' and negations of variables
+pub fn is_nnf(ast: &Expr) -> bool {
+    match ast {
+        Expr::Const(_) | Expr::Var(_) => true,
+        Expr::Not(operand) => matches!(**operand, Expr::Var(_)),
+        Expr::Binary(Op::And | Op::Or, left, right) => is_nnf(left) && is_nnf(right),
+        Expr::Binary(_, _, _) => false,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ex03::assignment::Assignment;
+    use crate::ex03::boolean_evaluation::eval_with;
+    use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+
+    #[test]
+    fn test_eliminate_derived_operators() {
+        let eliminate = |formula: &str| expr_to_postfix_string(&eliminate_derived_operators(&parse_expr(formula).unwrap()));
+        assert_eq!(eliminate("AB&"), "AB&");
+        assert_eq!(eliminate("AB>"), "A!B|");
+        assert_eq!(eliminate("AB="), "AB&A!B!&|");
+        assert_eq!(eliminate("AB^"), "AB!&A!B&|");
+        assert_eq!(eliminate("AB>!"), "A!B|!");
+        assert_eq!(eliminate("AB>C|"), "A!B|C|");
+    }
 
     #[test]
     fn test_nnf_conversion_postfix() {
@@ -105,5 +189,43 @@ mod tests {
         assert_eq!(negation_normal_form("AB&C|"), "AB&C|"); // (A & B) | C -> A & B | C
         assert_eq!(negation_normal_form("AB|C&"), "AB|C&"); // A | (B & C) -> A | B & C
         assert_eq!(negation_normal_form("AB|C!&"), "AB|C!&"); // A | (B & !C) -> A | B & !C
+
+        // 중첩된 동치 연산자
+        assert_eq!(negation_normal_form("AB>C>"), "AB!&C|"); // (A > B) > C -> (A & !B) | C
+        assert_eq!(negation_normal_form("AB=C="), "AB&A!B!&|C&A!B!|AB|&C!&|");
+
+        // 부정된 '^', '>', '=' 와 상수
+        assert_eq!(negation_normal_form("AB^!"), "A!B|AB!|&"); // !(A ^ B) -> (!A | B) & (A | !B)
+        assert_eq!(negation_normal_form("AB>!!"), "A!B|"); // !!(A > B) -> !A | B
+        assert_eq!(negation_normal_form("AB&C>!"), "AB&C!&"); // !((A & B) > C) -> A & B & !C
+        assert_eq!(negation_normal_form("0!"), "1");
+        assert_eq!(negation_normal_form("1!"), "0");
+        assert_eq!(negation_normal_form("A1&!"), "A!0|"); // !(A & 1) -> !A | 0
+
+        // 잘못된 입력 처리
+        assert_eq!(try_negation_normal_form("AB|!"), Ok("A!B!&".to_string()));
+        assert_eq!(try_negation_normal_form("AB|!|"), Err(FormulaError::StackUnderflow { token: 4, operator: '|' }));
+    }
+
+    #[test]
+    fn test_is_nnf() {
+        assert!(is_nnf(&parse_expr("A!B|C&").unwrap()));
+        assert!(is_nnf(&parse_expr("1").unwrap()));
+        assert!(!is_nnf(&parse_expr("AB|!").unwrap()));
+        assert!(!is_nnf(&parse_expr("AB>").unwrap()));
+        assert!(!is_nnf(&parse_expr("A!!").unwrap()));
+        assert!(!is_nnf(&parse_expr("0!").unwrap()));
+
+        // 변환 결과는 항상 NNF이고 원래 식과 동치
+        for formula in ["AB^!", "AB>!", "AB=!", "AB^C=!", "AB>C^D=!!", "A!B>!C0^=", "AB|C&!D>!"] {
+            let ast = parse_expr(formula).unwrap();
+            let nnf_ast = nnf(&ast);
+            assert!(is_nnf(&nnf_ast), "{}", formula);
+            let vars = extract_and_sort_vars_from_ast(&ast);
+            for row in 0..1u64 << vars.len() {
+                let assignment = Assignment::from_row(&vars, row);
+                assert_eq!(eval_with(&nnf_ast, &assignment), eval_with(&ast, &assignment), "{}", formula);
+            }
+        }
     }
 }
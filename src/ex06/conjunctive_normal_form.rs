@@ -1,94 +1,192 @@
-use crate::ex03::ast::{ASTNode, get_ast, ast_to_postfix_string};
+use crate::ex03::ast::{Expr, Op, parse_expr, expr_to_postfix_string};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
 use crate::ex05::negation_normal_form::nnf;
+use super::tseitin::plaisted_greenbaum;
+
+// CNF 변환 방식
+// - Equivalent: 분배 법칙으로 동치인 CNF를 만든다 (최악의 경우 지수 크기)
+// - Equisatisfiable: 보조 변수를 도입하여 충족 가능성만 같은 선형 크기의 CNF를 만든다
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CnfMode {
+    #[default]
+    Equivalent,
+    Equisatisfiable,
+}
+
+// 지정한 방식으로 CNF 변환
+pub fn cnf_with_mode(ast: &Expr, mode: CnfMode) -> Expr {
+    match mode {
+        CnfMode::Equivalent => cnf(&nnf(ast)),
+        CnfMode::Equisatisfiable => plaisted_greenbaum(ast).cnf,
+    }
+}
+
+// 리터럴: 변수와 부정 여부
+pub type Literal = (Symbol, bool);
+type Clause = Vec<Literal>;
 
 // CNF 변환의 메인 함수
-pub fn cnf(ast: &ASTNode) -> ASTNode {
-    match ast {
-        // 기본적인 피연산자는 그대로 유지
-        ASTNode::Operand(_) => ast.clone(),
+// NNF로 바꾼 뒤 OR를 AND 위로 분배하여 절 목록을 만들고, 절을 정리한 다음 다시 트리로 만든다
+pub fn cnf(ast: &Expr) -> Expr {
+    build_cnf(&cnf_clauses(ast))
+}
 
-        // OR 연산자에 대한 처리
-        ASTNode::Operator('|', left, Some(right)) => cnf_or(left, right),
+// cnf가 트리로 만들기 전의 정리된 절 목록
+pub fn cnf_clauses(ast: &Expr) -> Vec<Vec<Literal>> {
+    simplify_clauses(clauses_of(&nnf(ast)))
+}
 
-        // AND 연산자에 대한 처리
-        ASTNode::Operator('&', left, right_opt) => cnf_and(left, right_opt),
+// NNF 트리를 절 목록으로 변환. 이미 CNF 모양인 트리는 절과 리터럴 순서를 그대로 유지
+pub fn clauses_of(ast: &Expr) -> Vec<Vec<Literal>> {
+    match ast {
+        // 상수 1은 절이 없는 식, 상수 0은 빈 절 하나
+        Expr::Const(true) => vec![],
+        Expr::Const(false) => vec![vec![]],
+        Expr::Var(var) => vec![vec![(*var, false)]],
+        Expr::Not(operand) => match &**operand {
+            Expr::Var(var) => vec![vec![(*var, true)]],
+            Expr::Const(value) => clauses_of(&Expr::Const(!value)),
+            _ => clauses_of(&nnf(ast)),
+        },
 
-        // 나머지 연산자에 대해 CNF를 적용하여 재귀적으로 변환
-        ASTNode::Operator(op, left, right_opt) => {
-            ASTNode::Operator(*op, Box::new(cnf(left)), right_opt.as_ref().map(|r| Box::new(cnf(r))))
+        // AND는 양쪽 절 목록을 이어 붙임
+        Expr::Binary(Op::And, left, right) => {
+            let mut clauses = clauses_of(left);
+            clauses.extend(clauses_of(right));
+            clauses
         }
+
+        // OR는 분배 법칙: (a & b) | (c & d) -> (a | c) & (a | d) & (b | c) & (b | d)
+        Expr::Binary(Op::Or, left, right) => {
+            let left_clauses = clauses_of(left);
+            let right_clauses = clauses_of(right);
+            let mut clauses = Vec::with_capacity(left_clauses.len() * right_clauses.len());
+            for left_clause in &left_clauses {
+                for right_clause in &right_clauses {
+                    let mut clause = left_clause.clone();
+                    clause.extend(right_clause.iter().copied());
+                    clauses.push(clause);
+                }
+            }
+            clauses
+        }
+
+        // '>', '=', '^'는 NNF 변환에서 제거한 뒤 다시 처리
+        Expr::Binary(Op::Implies | Op::Iff | Op::Xor, _, _) => clauses_of(&nnf(ast)),
     }
 }
 
-// OR 연산자에 대한 CNF 처리 함수
-fn cnf_or(left: &ASTNode, right: &ASTNode) -> ASTNode {
-    let mut flattened_ors = vec![];
-
-    // OR 연산자들을 플랫하게 만든다
-    flatten_or(&cnf(left), &mut flattened_ors);
-    flatten_or(&cnf(right), &mut flattened_ors);
+// 절 정리: 중복 리터럴 제거, 항진 절(A | !A) 제거, 다른 절에 포함되는 절 제거
+// 처음 나온 순서는 유지. DNF의 항(모순 항 A & !A 제거, 흡수 법칙)에도 그대로 사용
+pub fn simplify_clauses(clauses: Vec<Vec<Literal>>) -> Vec<Vec<Literal>> {
+    let mut simplified: Vec<Clause> = Vec::new();
+    for clause in clauses {
+        let mut unique: Clause = Vec::with_capacity(clause.len());
+        for literal in clause {
+            if !unique.contains(&literal) {
+                unique.push(literal);
+            }
+        }
+        if unique.iter().any(|&(var, negated)| unique.contains(&(var, !negated))) {
+            continue;
+        }
+        simplified.push(unique);
+    }
 
-    // 플랫한 OR 연산자를 하나의 트리로 재구성
-    let mut current_ast = flattened_ors.pop().unwrap();  // 마지막 OR 노드로 시작
-    while let Some(next) = flattened_ors.pop() {
-        current_ast = ASTNode::Operator('|', Box::new(next), Some(Box::new(current_ast)));  // 트리를 재구성
+    // 흡수(subsumption): 절 C가 절 D의 부분집합이면 D는 필요 없음
+    let mut kept: Vec<Clause> = Vec::new();
+    for (index, clause) in simplified.iter().enumerate() {
+        let subsumed = simplified.iter().enumerate().any(|(other_index, other)| {
+            other_index != index
+                && is_subset(other, clause)
+                // 같은 절이 여러 번 나오면 처음 것만 남김
+                && (other.len() < clause.len() || other_index < index)
+        });
+        if !subsumed {
+            kept.push(clause.clone());
+        }
     }
-    current_ast
+    kept
 }
 
+fn is_subset(small: &Clause, large: &Clause) -> bool {
+    small.iter().all(|literal| large.contains(literal))
+}
 
-// AND 연산자에 대한 CNF 처리 함수
-fn cnf_and(left: &ASTNode, right_opt: &Option<Box<ASTNode>>) -> ASTNode {
-    let mut flattened_ands = vec![];
-    flatten_and(&cnf(left), &mut flattened_ands);
-    if let Some(right_node) = right_opt {
-        flatten_and(&cnf(right_node), &mut flattened_ands);
+// 절 목록을 오른쪽으로 중첩된 AND/OR 트리로 변환
+fn build_cnf(clauses: &[Clause]) -> Expr {
+    if clauses.iter().any(|clause| clause.is_empty()) {
+        return Expr::Const(false);
     }
+    let mut clause_exprs: Vec<Expr> = clauses
+        .iter()
+        .map(|clause| {
+            let mut literals: Vec<Expr> = clause.iter().map(|&literal| literal_expr(literal)).collect();
+            let mut current_ast = literals.pop().unwrap();
+            while let Some(next) = literals.pop() {
+                current_ast = Expr::or(next, current_ast);
+            }
+            current_ast
+        })
+        .collect();
 
-    let mut current_ast = flattened_ands.pop().unwrap();
-    while let Some(next) = flattened_ands.pop() {
-        current_ast = ASTNode::Operator('&', Box::new(next), Some(Box::new(current_ast)));
+    let mut current_ast = match clause_exprs.pop() {
+        Some(last) => last,
+        None => return Expr::Const(true),
+    };
+    while let Some(next) = clause_exprs.pop() {
+        current_ast = Expr::and(next, current_ast);
     }
     current_ast
 }
 
+pub fn literal_expr((var, negated): Literal) -> Expr {
+    if negated { Expr::negate(Expr::Var(var)) } else { Expr::Var(var) }
+}
 
-// OR 연산자를 플랫하게 만드는 함수
-fn flatten_or(ast: &ASTNode, nodes: &mut Vec<ASTNode>) {
+// 식이 CNF 모양(리터럴의 OR로 이루어진 절들의 AND)인지 확인하는 함수
+pub fn is_cnf(ast: &Expr) -> bool {
     match ast {
-        // 중첩된 OR 연산자를 플랫하게 만든다
-        ASTNode::Operator('|', left, Some(right)) => {
-            flatten_or(left, nodes);  // 왼쪽 서브트리에서 재귀적으로 플랫
-            flatten_or(right, nodes); // 오른쪽 서브트리에서 재귀적으로 플랫
-        }
-        // OR 연산자가 아닌 노드(피연산자 등)는 그대로 추가
-        _ => nodes.push(ast.clone()),
+        Expr::Binary(Op::And, left, right) => is_cnf(left) && is_cnf(right),
+        _ => is_clause(ast),
     }
 }
 
-// AND 연산자를 가진 노드들을 플랫하게 만드는 함수
-fn flatten_and(ast: &ASTNode, nodes: &mut Vec<ASTNode>) {
+fn is_clause(ast: &Expr) -> bool {
     match ast {
-        ASTNode::Operator('&', left, Some(right)) => {
-            flatten_and(left, nodes);
-            flatten_and(right, nodes);
-        }
-        _ => nodes.push(ast.clone()),
+        Expr::Binary(Op::Or, left, right) => is_clause(left) && is_clause(right),
+        Expr::Const(_) | Expr::Var(_) => true,
+        Expr::Not(operand) => matches!(**operand, Expr::Var(_)),
+        Expr::Binary(_, _, _) => false,
     }
 }
 
 
 /// 주어진 논리식을 CNF로 변환하는 함수
 pub fn conjunctive_normal_form(formula: &str) -> String {
-    let ast = get_ast(formula).expect("Failed to parse formula");  // AST를 생성
+    try_conjunctive_normal_form(formula).unwrap_or_else(|e| panic!("Failed to parse formula: {}", e))
+}
+
+pub fn try_conjunctive_normal_form(formula: &str) -> Result<String, FormulaError> {
+    let ast = parse_expr(formula)?;  // AST를 생성
     let nnf_ast = nnf(&ast);  // NNF로 변환
     let cnf_ast = cnf(&nnf_ast);  // CNF로 변환
-    ast_to_postfix_string(&cnf_ast)  // 결과를 후위 표기법 문자열로 반환
+    Ok(expr_to_postfix_string(&cnf_ast))  // 결과를 후위 표기법 문자열로 반환
+}
+
+/// 지정한 방식으로 CNF 변환한 결과를 후위 표기법 문자열로 반환하는 함수
+pub fn try_conjunctive_normal_form_with_mode(formula: &str, mode: CnfMode) -> Result<String, FormulaError> {
+    let ast = parse_expr(formula)?;
+    Ok(expr_to_postfix_string(&cnf_with_mode(&ast, mode)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ex03::assignment::Assignment;
+    use crate::ex03::boolean_evaluation::eval_with;
+    use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
 
     #[test]
     fn test_cnf_conversion() {
@@ -107,7 +205,59 @@ mod tests {
         assert_eq!(conjunctive_normal_form("ABC||"), "ABC||");       // 세 항 논리합은 그대로 유지
         assert_eq!(conjunctive_normal_form("ABC||!"), "A!B!C!&&");   // !(A | B | C) -> !A & !B & !C
         assert_eq!(conjunctive_normal_form("ABC|&"), "ABC|&");     // CNF 변환 결과 그대로
-        assert_eq!(conjunctive_normal_form("ABC&|"), "ABC&|");     // CNF 변환 결과 그대로
+        assert_eq!(conjunctive_normal_form("ABC&|"), "AB|AC|&");   // A | (B & C) -> (A | B) & (A | C)
         assert_eq!(conjunctive_normal_form("ABC&|!"), "A!B!C!|&");   // !(A | (B & C)) -> !A | !B | !C
+
+        // 파서가 유지한 '>', '=', '^'도 CNF에서 제거
+        assert_eq!(expr_to_postfix_string(&cnf(&parse_expr("AB>").unwrap())), "A!B|");
+        assert_eq!(conjunctive_normal_form("AB>C&"), "A!B|C&");
+
+        // 분배 법칙
+        assert_eq!(conjunctive_normal_form("AB&C|"), "AC|BC|&");
+        assert_eq!(conjunctive_normal_form("AB&CD&|"), "AC|AD|BC|BD|&&&");
+        assert_eq!(conjunctive_normal_form("AB=C|"), "AB!C||BA!C||&");
+
+        // 중복 리터럴, 항진 절, 흡수되는 절 제거
+        assert_eq!(conjunctive_normal_form("AA|B|"), "AB|");
+        assert_eq!(conjunctive_normal_form("AA!|B&"), "B");
+        assert_eq!(conjunctive_normal_form("AA!|"), "1");
+        assert_eq!(conjunctive_normal_form("AA!&"), "AA!&");
+        assert_eq!(conjunctive_normal_form("AB|A&"), "A");
+        assert_eq!(conjunctive_normal_form("AAB&|"), "A");
+        assert_eq!(conjunctive_normal_form("AB|BA|&"), "AB|");
+        assert_eq!(conjunctive_normal_form("A0&"), "0");
+        assert_eq!(conjunctive_normal_form("A0|"), "A");
+
+        // 변환 방식 선택
+        assert_eq!(try_conjunctive_normal_form_with_mode("AB|C&", CnfMode::Equivalent), Ok("AB|C&".to_string()));
+        assert_eq!(
+            try_conjunctive_normal_form_with_mode("AB&C|", CnfMode::Equisatisfiable),
+            Ok("[t1]!A|[t1]!B|[t2]![t1]C||[t2]&&&".to_string())
+        );
+
+        // 잘못된 입력 처리
+        assert_eq!(try_conjunctive_normal_form("A&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+        assert_eq!(try_conjunctive_normal_form("AB"), Err(FormulaError::LeftoverOperands { count: 2 }));
+    }
+
+    #[test]
+    fn test_cnf_shape() {
+        assert!(is_cnf(&parse_expr("AB|C!&").unwrap()));
+        assert!(is_cnf(&parse_expr("A!").unwrap()));
+        assert!(!is_cnf(&parse_expr("AB&C|").unwrap()));
+        assert!(!is_cnf(&parse_expr("AB|!").unwrap()));
+        assert!(!is_cnf(&parse_expr("AB>").unwrap()));
+
+        // 변환 결과는 항상 CNF이고 원래 식과 동치
+        for formula in ["AB&C|", "AB=C^", "AB>C>!", "AB&CD&|EF&|", "AB^!C&D|", "AB|C!&!", "ABC==D>"] {
+            let ast = parse_expr(formula).unwrap();
+            let cnf_ast = cnf(&ast);
+            assert!(is_cnf(&cnf_ast), "{}", formula);
+            let vars = extract_and_sort_vars_from_ast(&ast);
+            for row in 0..1u64 << vars.len() {
+                let assignment = Assignment::from_row(&vars, row);
+                assert_eq!(eval_with(&cnf_ast, &assignment), eval_with(&ast, &assignment), "{}", formula);
+            }
+        }
     }
 }
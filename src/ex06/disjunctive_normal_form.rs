@@ -0,0 +1,248 @@
+use crate::ex03::ast::{ast_to_string, parse_formula, Expr, Notation, Op};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::{evaluate_truth_table, extract_and_sort_vars_from_ast};
+use crate::ex05::negation_normal_form::nnf;
+use super::conjunctive_normal_form::{literal_expr, simplify_clauses, Literal};
+
+// DNF와 정규형(canonical form)
+// 결과는 왼쪽으로 중첩하여 중위 표기에서 "A & B | !A & C"처럼 괄호 없이 읽히도록 만든다
+
+type Term = Vec<Literal>;
+
+// DNF 변환의 메인 함수
+// NNF로 바꾼 뒤 AND를 OR 위로 분배하여 항 목록을 만들고, 모순 항과 흡수되는 항을 제거
+pub fn dnf(ast: &Expr) -> Expr {
+    let terms = simplify_clauses(terms_of(&nnf(ast)));
+    build_sum(&terms)
+}
+
+// NNF 트리를 항(리터럴의 AND) 목록으로 변환
+fn terms_of(ast: &Expr) -> Vec<Term> {
+    match ast {
+        // 상수 1은 빈 항 하나, 상수 0은 항이 없는 식
+        Expr::Const(true) => vec![vec![]],
+        Expr::Const(false) => vec![],
+        Expr::Var(var) => vec![vec![(*var, false)]],
+        Expr::Not(operand) => match &**operand {
+            Expr::Var(var) => vec![vec![(*var, true)]],
+            Expr::Const(value) => terms_of(&Expr::Const(!value)),
+            _ => terms_of(&nnf(ast)),
+        },
+
+        // OR는 양쪽 항 목록을 이어 붙임
+        Expr::Binary(Op::Or, left, right) => {
+            let mut terms = terms_of(left);
+            terms.extend(terms_of(right));
+            terms
+        }
+
+        // AND는 분배 법칙: (a | b) & (c | d) -> (a & c) | (a & d) | (b & c) | (b & d)
+        Expr::Binary(Op::And, left, right) => {
+            let left_terms = terms_of(left);
+            let right_terms = terms_of(right);
+            let mut terms = Vec::with_capacity(left_terms.len() * right_terms.len());
+            for left_term in &left_terms {
+                for right_term in &right_terms {
+                    let mut term = left_term.clone();
+                    term.extend(right_term.iter().copied());
+                    terms.push(term);
+                }
+            }
+            terms
+        }
+
+        Expr::Binary(Op::Implies | Op::Iff | Op::Xor, _, _) => terms_of(&nnf(ast)),
+    }
+}
+
+// 리터럴 목록을 왼쪽으로 중첩된 트리로 변환. 빈 목록이면 항등원
+fn build_group(literals: &[Literal], op: Op) -> Expr {
+    let mut literals = literals.iter().map(|&literal| literal_expr(literal));
+    match literals.next() {
+        Some(first) => literals.fold(first, |acc, next| Expr::binary(op, acc, next)),
+        None => Expr::Const(op == Op::And),
+    }
+}
+
+// 항들의 OR (sum of products)
+fn build_sum(terms: &[Term]) -> Expr {
+    let mut terms = terms.iter().map(|term| build_group(term, Op::And));
+    match terms.next() {
+        Some(first) => terms.fold(first, Expr::or),
+        None => Expr::Const(false),
+    }
+}
+
+// 절들의 AND (product of sums)
+fn build_product(clauses: &[Vec<Literal>]) -> Expr {
+    let mut clauses = clauses.iter().map(|clause| build_group(clause, Op::Or));
+    match clauses.next() {
+        Some(first) => clauses.fold(first, Expr::and),
+        None => Expr::Const(true),
+    }
+}
+
+// 식이 DNF 모양(리터럴의 AND로 이루어진 항들의 OR)인지 확인하는 함수
+pub fn is_dnf(ast: &Expr) -> bool {
+    match ast {
+        Expr::Binary(Op::Or, left, right) => is_dnf(left) && is_dnf(right),
+        _ => is_term(ast),
+    }
+}
+
+fn is_term(ast: &Expr) -> bool {
+    match ast {
+        Expr::Binary(Op::And, left, right) => is_term(left) && is_term(right),
+        Expr::Const(_) | Expr::Var(_) => true,
+        Expr::Not(operand) => matches!(**operand, Expr::Var(_)),
+        Expr::Binary(_, _, _) => false,
+    }
+}
+
+// 진리표에서 결과가 1인 행마다 최소항(minterm)을 만들어 OR로 연결
+// 변수 순서와 행 순서는 print_truth_table과 같음
+pub fn sum_of_minterms(ast: &Expr) -> Result<Expr, FormulaError> {
+    let vars = extract_and_sort_vars_from_ast(ast);
+    let results = evaluate_truth_table(ast, &vars)?;
+    let minterms: Vec<Term> = results
+        .iter()
+        .enumerate()
+        .filter(|&(_, &result)| result)
+        .map(|(row, _)| row_literals(&vars, row, false))
+        .collect();
+    Ok(build_sum(&minterms))
+}
+
+// 진리표에서 결과가 0인 행마다 최대항(maxterm)을 만들어 AND로 연결
+pub fn product_of_maxterms(ast: &Expr) -> Result<Expr, FormulaError> {
+    let vars = extract_and_sort_vars_from_ast(ast);
+    let results = evaluate_truth_table(ast, &vars)?;
+    let maxterms: Vec<Vec<Literal>> = results
+        .iter()
+        .enumerate()
+        .filter(|&(_, &result)| !result)
+        .map(|(row, _)| row_literals(&vars, row, true))
+        .collect();
+    Ok(build_product(&maxterms))
+}
+
+// 행에서만 0이 되는 최대항. 1인 변수는 부정하여 왼쪽으로 중첩
+pub fn maxterm(vars: &[Symbol], row: usize) -> Expr {
+    build_group(&row_literals(vars, row, true), Op::Or)
+}
+
+// 행 번호의 비트로 리터럴을 만듦 (첫 번째 변수가 최상위 비트)
+// 최소항은 1인 변수를 그대로, 최대항은 1인 변수를 부정하여 그 행에서만 0이 되게 함
+fn row_literals(vars: &[Symbol], row: usize, maxterm: bool) -> Vec<Literal> {
+    vars.iter()
+        .enumerate()
+        .map(|(i, &var)| {
+            let bit = row >> (vars.len() - 1 - i) & 1 == 1;
+            (var, bit == maxterm)
+        })
+        .collect()
+}
+
+/// 주어진 논리식을 DNF로 변환하는 함수
+pub fn disjunctive_normal_form(formula: &str) -> String {
+    try_disjunctive_normal_form(formula).unwrap_or_else(|e| panic!("Failed to parse formula: {}", e))
+}
+
+pub fn try_disjunctive_normal_form(formula: &str) -> Result<String, FormulaError> {
+    try_disjunctive_normal_form_with_notation(formula, Notation::Postfix)
+}
+
+/// 주어진 표기법으로 읽고, 같은 표기법으로 결과를 반환
+pub fn try_disjunctive_normal_form_with_notation(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&dnf(&ast), notation))
+}
+
+/// 최소항의 합(정규 DNF)을 주어진 표기법으로 반환하는 함수
+pub fn try_sum_of_minterms(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&sum_of_minterms(&ast)?, notation))
+}
+
+/// 최대항의 곱(정규 CNF)을 주어진 표기법으로 반환하는 함수
+pub fn try_product_of_maxterms(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&product_of_maxterms(&ast)?, notation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::assignment::Assignment;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex03::boolean_evaluation::eval_with;
+    use crate::ex06::conjunctive_normal_form::is_cnf;
+
+    fn assert_equivalent(left: &Expr, right: &Expr) {
+        let vars = extract_and_sort_vars_from_ast(&Expr::and(left.clone(), right.clone()));
+        for row in 0..1u64 << vars.len() {
+            let assignment = Assignment::from_row(&vars, row);
+            assert_eq!(eval_with(left, &assignment), eval_with(right, &assignment), "{} / {}", left, right);
+        }
+    }
+
+    #[test]
+    fn test_dnf_conversion() {
+        assert_eq!(disjunctive_normal_form("A"), "A");
+        assert_eq!(disjunctive_normal_form("AB&!"), "A!B!|");
+        assert_eq!(disjunctive_normal_form("AB|!"), "A!B!&");
+        assert_eq!(disjunctive_normal_form("AB|C&"), "AC&BC&|");
+        assert_eq!(disjunctive_normal_form("AB&C|"), "AB&C|");
+        assert_eq!(disjunctive_normal_form("AB|CD|&"), "AC&AD&|BC&|BD&|");
+        assert_eq!(disjunctive_normal_form("AB>"), "A!B|");
+        assert_eq!(disjunctive_normal_form("AB^"), "AB!&A!B&|");
+
+        // 모순 항과 흡수되는 항 제거
+        assert_eq!(disjunctive_normal_form("AA!&B|"), "B");
+        assert_eq!(disjunctive_normal_form("AA!&"), "0");
+        assert_eq!(disjunctive_normal_form("AAB&|"), "A");
+        assert_eq!(disjunctive_normal_form("AA!|"), "AA!|");
+
+        // 중위 표기
+        assert_eq!(
+            try_disjunctive_normal_form_with_notation("(A | B) & !C", Notation::Infix),
+            Ok("A & !C | B & !C".to_string())
+        );
+        assert_eq!(try_disjunctive_normal_form("A&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+
+        for formula in ["AB&C|", "AB=C^", "AB>C>!", "AB|CD|&EF|&", "AB^!C&D|", "ABC==D>"] {
+            let ast = parse_expr(formula).unwrap();
+            let dnf_ast = dnf(&ast);
+            assert!(is_dnf(&dnf_ast), "{}", formula);
+            assert_equivalent(&dnf_ast, &ast);
+        }
+        assert!(!is_dnf(&parse_expr("AB|C&").unwrap()));
+        assert!(is_dnf(&parse_expr("AB&C!|").unwrap()));
+    }
+
+    #[test]
+    fn test_canonical_forms() {
+        assert_eq!(try_sum_of_minterms("AB^", Notation::Postfix), Ok("A!B&AB!&|".to_string()));
+        assert_eq!(try_product_of_maxterms("AB^", Notation::Postfix), Ok("AB|A!B!|&".to_string()));
+        assert_eq!(
+            try_sum_of_minterms("A > B", Notation::Infix),
+            Ok("!A & !B | !A & B | A & B".to_string())
+        );
+        assert_eq!(try_product_of_maxterms("A > B", Notation::Infix), Ok("!A | B".to_string()));
+
+        // 항상 참/거짓인 식
+        assert_eq!(try_sum_of_minterms("AA!&", Notation::Postfix), Ok("0".to_string()));
+        assert_eq!(try_product_of_maxterms("AA!|", Notation::Postfix), Ok("1".to_string()));
+        assert_eq!(try_sum_of_minterms("1", Notation::Postfix), Ok("1".to_string()));
+
+        for formula in ["AB&C|", "AB=C^", "AB>C>!", "[x1][door]|A!&"] {
+            let ast = parse_expr(formula).unwrap();
+            let minterms = sum_of_minterms(&ast).unwrap();
+            let maxterms = product_of_maxterms(&ast).unwrap();
+            assert!(is_dnf(&minterms) && is_cnf(&maxterms), "{}", formula);
+            assert_equivalent(&minterms, &ast);
+            assert_equivalent(&maxterms, &ast);
+        }
+    }
+}
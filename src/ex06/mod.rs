@@ -1 +1,3 @@
-pub mod conjunctive_normal_form;
\ No newline at end of file
+pub mod conjunctive_normal_form;
+pub mod disjunctive_normal_form;
+pub mod tseitin;
\ No newline at end of file
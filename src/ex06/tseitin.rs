@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{Expr, Op};
+use crate::ex03::boolean_evaluation::partial_eval;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::extract_variables;
+
+// Tseitin 변환: 부분식마다 보조 변수를 도입하여 선형 크기의 CNF를 만든다.
+// 결과는 원래 식과 동치가 아니라 충족 가능성만 같다 (equisatisfiable).
+
+// 변환 결과. cnf는 절들의 AND, auxiliary는 새로 만든 보조 변수 (생성 순서)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encoding {
+    pub cnf: Expr,
+    pub auxiliary: Vec<Symbol>,
+}
+
+// 부분식이 어떤 극성(polarity)으로 나타나는지
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Polarity {
+    Positive,
+    Negative,
+    Both,
+}
+
+impl Polarity {
+    fn flip(self) -> Polarity {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Both => Polarity::Both,
+        }
+    }
+
+    fn positive(self) -> bool {
+        self != Polarity::Negative
+    }
+
+    fn negative(self) -> bool {
+        self != Polarity::Positive
+    }
+}
+
+// 모든 보조 변수에 대해 t <-> (a op b)의 양방향 절을 추가
+pub fn tseitin(ast: &Expr) -> Encoding {
+    Encoder::new(ast, false).encode(ast)
+}
+
+// Plaisted-Greenbaum: 부분식의 극성에 필요한 방향의 절만 추가
+pub fn plaisted_greenbaum(ast: &Expr) -> Encoding {
+    Encoder::new(ast, true).encode(ast)
+}
+
+struct Encoder {
+    used_names: HashSet<Symbol>,
+    next_index: usize,
+    polarity_aware: bool,
+    auxiliary: Vec<Symbol>,
+    clauses: Vec<Vec<Expr>>,
+}
+
+impl Encoder {
+    fn new(ast: &Expr, polarity_aware: bool) -> Encoder {
+        let mut used_names = HashSet::new();
+        extract_variables(ast, &mut used_names);
+        Encoder { used_names, next_index: 1, polarity_aware, auxiliary: vec![], clauses: vec![] }
+    }
+
+    fn encode(mut self, ast: &Expr) -> Encoding {
+        // 상수를 먼저 정리. 전체가 상수이면 그대로 반환
+        let simplified = partial_eval(ast, &Assignment::new());
+        if let Expr::Const(_) = simplified {
+            return Encoding { cnf: simplified, auxiliary: vec![] };
+        }
+        let root = self.literal(&simplified, Polarity::Positive);
+        self.clauses.push(vec![root]);
+
+        let clauses = self.clauses.into_iter().map(|clause| nest(clause, Op::Or)).collect();
+        Encoding { cnf: nest(clauses, Op::And), auxiliary: self.auxiliary }
+    }
+
+    // 기존 변수 이름과 겹치지 않는 보조 변수 t1, t2, ...
+    fn fresh(&mut self) -> Symbol {
+        loop {
+            let symbol = Symbol::intern(&format!("t{}", self.next_index));
+            self.next_index += 1;
+            if self.used_names.insert(symbol) {
+                self.auxiliary.push(symbol);
+                return symbol;
+            }
+        }
+    }
+
+    // 부분식을 대표하는 리터럴을 반환하고, 필요한 절을 추가
+    fn literal(&mut self, ast: &Expr, polarity: Polarity) -> Expr {
+        let (op, left, right) = match ast {
+            Expr::Var(_) | Expr::Const(_) => return ast.clone(),
+            Expr::Not(operand) => return negate(self.literal(operand, polarity.flip())),
+            Expr::Binary(op, left, right) => (*op, left, right),
+        };
+        let polarity = if self.polarity_aware { polarity } else { Polarity::Both };
+        let (left_polarity, right_polarity) = match op {
+            Op::And | Op::Or => (polarity, polarity),
+            Op::Implies => (polarity.flip(), polarity),
+            Op::Xor | Op::Iff => (Polarity::Both, Polarity::Both),
+        };
+        let a = self.literal(left, left_polarity);
+        let b = self.literal(right, right_polarity);
+        let t = Expr::Var(self.fresh());
+
+        // a > b는 !a | b와 같음
+        let (op, a) = match op {
+            Op::Implies => (Op::Or, negate(a)),
+            _ => (op, a),
+        };
+        let (forward, backward) = match op {
+            // t -> a & b / a & b -> t
+            Op::And => (
+                vec![vec![negate(t.clone()), a.clone()], vec![negate(t.clone()), b.clone()]],
+                vec![vec![negate(a), negate(b), t.clone()]],
+            ),
+            // t -> a | b / a | b -> t
+            Op::Or => (
+                vec![vec![negate(t.clone()), a.clone(), b.clone()]],
+                vec![vec![negate(a), t.clone()], vec![negate(b), t.clone()]],
+            ),
+            // t -> a ^ b / a ^ b -> t
+            Op::Xor => (
+                vec![vec![negate(t.clone()), a.clone(), b.clone()], vec![negate(t.clone()), negate(a.clone()), negate(b.clone())]],
+                vec![vec![t.clone(), negate(a.clone()), b.clone()], vec![t.clone(), a, negate(b)]],
+            ),
+            // t -> (a = b) / (a = b) -> t
+            Op::Iff => (
+                vec![vec![negate(t.clone()), negate(a.clone()), b.clone()], vec![negate(t.clone()), a.clone(), negate(b.clone())]],
+                vec![vec![t.clone(), a.clone(), b.clone()], vec![t.clone(), negate(a), negate(b)]],
+            ),
+            Op::Implies => unreachable!("implication is rewritten as a disjunction"),
+        };
+        if polarity.positive() {
+            self.clauses.extend(forward);
+        }
+        if polarity.negative() {
+            self.clauses.extend(backward);
+        }
+        t
+    }
+}
+
+// 이중 부정을 만들지 않는 부정
+fn negate(literal: Expr) -> Expr {
+    match literal {
+        Expr::Not(operand) => *operand,
+        _ => Expr::negate(literal),
+    }
+}
+
+// [a, b, c] -> a op (b op c)
+fn nest(mut exprs: Vec<Expr>, op: Op) -> Expr {
+    let mut result = exprs.pop().expect("at least one expression");
+    while let Some(expr) = exprs.pop() {
+        result = Expr::binary(op, expr, result);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::{expr_to_postfix_string, parse_expr};
+    use crate::ex03::boolean_evaluation::eval_with;
+    use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+    use crate::ex06::conjunctive_normal_form::is_cnf;
+
+    // 모든 할당을 전수 조사하여 모델 수를 셈
+    fn count_models(ast: &Expr) -> usize {
+        let vars = extract_and_sort_vars_from_ast(ast);
+        (0..1u64 << vars.len())
+            .filter(|&row| eval_with(ast, &Assignment::from_row(&vars, row)).unwrap())
+            .count()
+    }
+
+    #[test]
+    fn test_tseitin() {
+        let encoding = tseitin(&parse_expr("AB&C|").unwrap());
+        assert_eq!(encoding.auxiliary, vec![Symbol::from("t1"), Symbol::from("t2")]);
+        assert_eq!(
+            expr_to_postfix_string(&encoding.cnf),
+            "[t1]!A|[t1]!B|A!B![t1]||[t2]![t1]C||[t1]![t2]|C![t2]|[t2]&&&&&&"
+        );
+
+        // Tseitin은 원래 식의 모델마다 정확히 하나로 확장되므로 모델 수가 같음
+        for formula in ["AB&C|", "AB^C=", "AB>C>!", "AB|C!&D^", "AB=!CD>&", "AA^", "A1&B|", "AB&!"] {
+            let ast = parse_expr(formula).unwrap();
+            let encoding = tseitin(&ast);
+            assert!(is_cnf(&encoding.cnf), "{}", formula);
+            assert_eq!(count_models(&encoding.cnf), count_models(&ast), "{}", formula);
+        }
+
+        // 상수는 미리 정리
+        assert_eq!(tseitin(&parse_expr("A1|").unwrap()).cnf, Expr::Const(true));
+        assert_eq!(tseitin(&parse_expr("A0&").unwrap()).cnf, Expr::Const(false));
+        assert_eq!(tseitin(&parse_expr("A!").unwrap()).cnf, parse_expr("A!").unwrap());
+    }
+
+    #[test]
+    fn test_plaisted_greenbaum() {
+        // 양의 극성만 있으면 한쪽 방향의 절만 추가
+        let encoding = plaisted_greenbaum(&parse_expr("AB&C|").unwrap());
+        assert_eq!(expr_to_postfix_string(&encoding.cnf), "[t1]!A|[t1]!B|[t2]![t1]C||[t2]&&&");
+
+        for formula in ["AB&C|", "AB^C=", "AB>C>!", "AB|C!&D^", "AB=!CD>&", "AA^", "AA!&B|!", "AB&!"] {
+            let ast = parse_expr(formula).unwrap();
+            let encoding = plaisted_greenbaum(&ast);
+            assert!(is_cnf(&encoding.cnf), "{}", formula);
+            // 충족 가능성이 같고, 인코딩의 모든 모델은 원래 식을 만족
+            assert_eq!(count_models(&encoding.cnf) > 0, count_models(&ast) > 0, "{}", formula);
+            let vars = extract_and_sort_vars_from_ast(&encoding.cnf);
+            for row in 0..1u64 << vars.len() {
+                let assignment = Assignment::from_row(&vars, row);
+                if eval_with(&encoding.cnf, &assignment).unwrap() {
+                    assert_eq!(eval_with(&ast, &assignment), Ok(true), "{}", formula);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fresh_names_and_size() {
+        // 이미 사용 중인 이름은 건너뜀
+        let encoding = tseitin(&parse_expr("[t1][t3]&A|").unwrap());
+        assert_eq!(encoding.auxiliary, vec![Symbol::from("t2"), Symbol::from("t4")]);
+
+        // (A1&B1)|(A2&B2)|... 는 분배하면 2^n개의 절이 필요하지만 인코딩은 선형 크기
+        let n = 40;
+        let mut ast = Expr::and(Expr::var("a0"), Expr::var("b0"));
+        for i in 1..n {
+            ast = Expr::or(ast, Expr::and(Expr::var(&format!("a{}", i)), Expr::var(&format!("b{}", i))));
+        }
+        let encoding = plaisted_greenbaum(&ast);
+        let mut clauses = 0;
+        let mut current = &encoding.cnf;
+        while let Expr::Binary(Op::And, _, right) = current {
+            clauses += 1;
+            current = right;
+        }
+        assert_eq!(encoding.auxiliary.len(), 2 * n - 1);
+        assert!(clauses < 4 * n, "{} clauses", clauses);
+    }
+}
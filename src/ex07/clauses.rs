@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::ex03::ast::{Expr, Op};
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+use crate::ex06::conjunctive_normal_form::{clauses_of, cnf_clauses, CnfMode, Literal};
+use crate::ex06::tseitin::{plaisted_greenbaum, Encoding};
+
+// 리터럴: 변수 번호와 부호를 하나의 정수로 표현 (2 * var + negated)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn new(var: usize, negated: bool) -> Lit {
+        Lit((var as u32) << 1 | negated as u32)
+    }
+
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    // watch 리스트 등에서 배열 인덱스로 사용
+    pub fn code(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl std::ops::Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+impl fmt::Debug for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", if self.is_negated() { "-" } else { "" }, self.var())
+    }
+}
+
+pub type Clause = Vec<Lit>;
+
+// 평평한 절(clause) 데이터베이스. 변수 번호 i는 variables[i]에 대응
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClauseSet {
+    pub variables: Vec<Symbol>,
+    pub clauses: Vec<Clause>,
+}
+
+impl ClauseSet {
+    // cnf의 결과를 절 단위로 펼쳐서 만듦. 변수 번호는 진리표 열 순서와 같음
+    pub fn from_expr(ast: &Expr) -> ClauseSet {
+        ClauseSet::over(extract_and_sort_vars_from_ast(ast), &cnf_clauses(ast))
+    }
+
+    // Equisatisfiable 방식이면 원래 변수 뒤에 보조 변수가 붙음
+    pub fn from_expr_with_mode(ast: &Expr, mode: CnfMode) -> ClauseSet {
+        match mode {
+            CnfMode::Equivalent => ClauseSet::from_expr(ast),
+            CnfMode::Equisatisfiable => ClauseSet::from_encoding(ast, &plaisted_greenbaum(ast)),
+        }
+    }
+
+    // tseitin/plaisted_greenbaum의 결과를 펼침. 원래 변수 뒤에 보조 변수가 붙음
+    pub fn from_encoding(ast: &Expr, encoding: &Encoding) -> ClauseSet {
+        let mut variables = extract_and_sort_vars_from_ast(ast);
+        variables.extend(encoding.auxiliary.iter().copied());
+        ClauseSet::over(variables, &clauses_of(&encoding.cnf))
+    }
+
+    // 이미 CNF 모양인 AST(cnf의 결과)를 그대로 절 단위로 펼침
+    pub fn from_cnf(cnf_ast: &Expr) -> ClauseSet {
+        ClauseSet::over(extract_and_sort_vars_from_ast(cnf_ast), &clauses_of(cnf_ast))
+    }
+
+    // 변수 번호 표를 한 번만 만들고 절 목록을 번호로 바꿈
+    fn over(variables: Vec<Symbol>, clauses: &[Vec<Literal>]) -> ClauseSet {
+        let indices = index_map(&variables);
+        let clauses = number_clauses(clauses, &indices);
+        ClauseSet { variables, clauses }
+    }
+
+    // 절 목록을 다시 AND/OR 트리로 변환 (cnf와 같이 오른쪽으로 중첩)
+    pub fn to_expr(&self) -> Expr {
+        let clause_exprs: Vec<Expr> = self
+            .clauses
+            .iter()
+            .map(|clause| {
+                let lits: Vec<Expr> = clause.iter().map(|&lit| self.lit_expr(lit)).collect();
+                nest(lits, Op::Or, Expr::Const(false))
+            })
+            .collect();
+        nest(clause_exprs, Op::And, Expr::Const(true))
+    }
+
+    // 변수 번호별 값으로 모든 절이 만족되는지 확인
+    pub fn is_satisfied_by(&self, values: &[bool]) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.iter().any(|&lit| values[lit.var()] != lit.is_negated()))
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.variables.len()
+    }
+
+    // 변수 하나의 번호. 여러 변수를 찾을 때는 var_indices로 표를 한 번 만들어 사용
+    pub fn var_index(&self, var: Symbol) -> Option<usize> {
+        self.variables.iter().position(|&v| v == var)
+    }
+
+    pub fn var_indices(&self) -> HashMap<Symbol, usize> {
+        index_map(&self.variables)
+    }
+
+    fn lit_expr(&self, lit: Lit) -> Expr {
+        let var = Expr::Var(self.variables[lit.var()]);
+        if lit.is_negated() { Expr::negate(var) } else { var }
+    }
+}
+
+// ex06의 절 목록을 변수 번호로 바꿈
+fn number_clauses(clauses: &[Vec<Literal>], indices: &HashMap<Symbol, usize>) -> Vec<Clause> {
+    clauses
+        .iter()
+        .map(|clause| clause.iter().map(|&(var, negated)| Lit::new(indices[&var], negated)).collect())
+        .collect()
+}
+
+fn index_map(variables: &[Symbol]) -> HashMap<Symbol, usize> {
+    variables.iter().enumerate().map(|(index, &var)| (var, index)).collect()
+}
+
+// [a, b, c] -> a op (b op c). 빈 목록이면 항등원
+fn nest(mut exprs: Vec<Expr>, op: Op, identity: Expr) -> Expr {
+    let mut result = match exprs.pop() {
+        Some(last) => last,
+        None => return identity,
+    };
+    while let Some(expr) = exprs.pop() {
+        result = Expr::binary(op, expr, result);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    #[test]
+    fn test_clause_set() {
+        let clause_set = ClauseSet::from_expr(&parse_expr("AB|C!&").unwrap());
+        assert_eq!(clause_set.variables, vec![Symbol::from('A'), Symbol::from('B'), Symbol::from('C')]);
+        assert_eq!(clause_set.clauses, vec![vec![Lit::new(0, false), Lit::new(1, false)], vec![Lit::new(2, true)]]);
+
+        // OR 아래의 AND는 분배되어 절로 펼쳐짐: A | (B & C) -> (A | B) & (A | C)
+        let clause_set = ClauseSet::from_expr(&parse_expr("ABC&|").unwrap());
+        assert_eq!(
+            clause_set.clauses,
+            vec![vec![Lit::new(0, false), Lit::new(1, false)], vec![Lit::new(0, false), Lit::new(2, false)]]
+        );
+
+        // 상수
+        assert_eq!(ClauseSet::from_expr(&parse_expr("A1|").unwrap()).clauses, Vec::<Clause>::new());
+        assert_eq!(ClauseSet::from_expr(&parse_expr("A0&").unwrap()).clauses, vec![Vec::<Lit>::new()]);
+    }
+
+    #[test]
+    fn test_clause_set_round_trip() {
+        let cnf_ast = parse_expr("AB!C||A!D|&").unwrap();
+        let clause_set = ClauseSet::from_cnf(&cnf_ast);
+        assert_eq!(clause_set.to_expr(), cnf_ast);
+        assert!(clause_set.is_satisfied_by(&[true, false, false, true]));
+        assert!(!clause_set.is_satisfied_by(&[true, true, false, false]));
+
+        assert_eq!(ClauseSet::default().to_expr(), Expr::Const(true));
+        let empty_clause = ClauseSet { variables: vec![], clauses: vec![vec![]] };
+        assert_eq!(empty_clause.to_expr(), Expr::Const(false));
+    }
+
+    #[test]
+    fn test_lit() {
+        let lit = Lit::new(3, true);
+        assert_eq!(lit.var(), 3);
+        assert!(lit.is_negated());
+        assert_eq!(!lit, Lit::new(3, false));
+        assert_eq!(!!lit, lit);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use crate::ex03::ast::Expr;
+use crate::ex03::symbol::{Symbol, MAX_INTERNED_BYTES};
+use super::clauses::{Clause, ClauseSet, Lit};
+
+// DIMACS CNF 형식 입출력
+//
+// c var 1 A
+// c var 2 B
+// p cnf 2 2
+// 1 -2 0
+// 2 0
+//
+// 변수 이름은 "c var <번호> <이름>" 주석으로 보존. 이름이 없는 변수는 x<번호>로 읽음 (인터닝하지 않음)
+// 헤더의 변수 개수는 범위 검사에만 쓰고, 절이나 이름 주석에 나오는 변수만 번호 순서대로 절 집합에 넣음
+// 이름 안의 공백 문자는 \u{20}처럼, 역슬래시는 \\로 이스케이프하여 한 단어로 씀
+
+// Lit은 변수 번호를 31비트에 저장
+pub const MAX_DIMACS_VARIABLES: usize = (u32::MAX >> 1) as usize;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DimacsError {
+    MissingHeader,
+    InvalidHeader { line: usize },
+    InvalidToken { line: usize, token: String },
+    VariableOutOfRange { line: usize, var: usize },
+    DuplicateVariableName { line: usize, name: String },
+    ClauseCountMismatch { expected: usize, found: usize },
+    TooManySymbols { line: usize, limit: usize },
+}
+
+impl fmt::Display for DimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DimacsError::MissingHeader => write!(f, "missing 'p cnf' header"),
+            DimacsError::InvalidHeader { line } => write!(f, "invalid header at line {}", line),
+            DimacsError::InvalidToken { line, token } => {
+                write!(f, "invalid token '{}' at line {}", token, line)
+            }
+            DimacsError::VariableOutOfRange { line, var } => {
+                write!(f, "variable {} at line {} is not declared in the header", var, line)
+            }
+            DimacsError::DuplicateVariableName { line, name } => {
+                write!(f, "variable name '{}' at line {} is already used", name, line)
+            }
+            DimacsError::ClauseCountMismatch { expected, found } => {
+                write!(f, "header declares {} clauses but {} were found", expected, found)
+            }
+            DimacsError::TooManySymbols { line, limit } => {
+                write!(f, "variable name at line {} exceeds the limit of {} bytes of names", line, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+// 절 집합을 DIMACS 문자열로 변환
+pub fn to_dimacs(clause_set: &ClauseSet) -> String {
+    let mut output = String::new();
+    for (index, var) in clause_set.variables.iter().enumerate() {
+        output.push_str(&format!("c var {} {}\n", index + 1, escape_name(&var.name())));
+    }
+    output.push_str(&format!("p cnf {} {}\n", clause_set.num_vars(), clause_set.clauses.len()));
+    for clause in &clause_set.clauses {
+        for &lit in clause {
+            output.push_str(&format!("{} ", dimacs_literal(lit)));
+        }
+        output.push_str("0\n");
+    }
+    output
+}
+
+// cnf의 결과를 절 단위로 펼쳐서 DIMACS 문자열로 변환
+pub fn cnf_to_dimacs(cnf_ast: &Expr) -> String {
+    to_dimacs(&ClauseSet::from_cnf(cnf_ast))
+}
+
+fn escape_name(name: &str) -> String {
+    let mut escaped = String::new();
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_whitespace() || c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// escape_name의 역. 잘못된 이스케이프면 None
+fn unescape_name(escaped: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            name.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => name.push('\\'),
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let rest = chars.as_str();
+                let end = rest.find('}')?;
+                name.push(char::from_u32(u32::from_str_radix(&rest[..end], 16).ok()?)?);
+                chars = rest[end + 1..].chars();
+            }
+            _ => return None,
+        }
+    }
+    Some(name)
+}
+
+fn dimacs_literal(lit: Lit) -> i64 {
+    let var = lit.var() as i64 + 1;
+    if lit.is_negated() { -var } else { var }
+}
+
+// DIMACS 문자열을 읽어 sat에서 사용할 수 있는 절 집합으로 변환
+pub fn parse_dimacs(input: &str) -> Result<ClauseSet, DimacsError> {
+    let mut names: HashMap<usize, (usize, Symbol)> = HashMap::new(); // 번호 -> (줄, 이름)
+    let mut header: Option<(usize, usize)> = None;
+    let mut clauses: Vec<Clause> = Vec::new();
+    let mut current: Clause = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('c') {
+            if let Some((var, name)) = parse_name_comment(comment) {
+                let symbol = Symbol::try_intern(&name)
+                    .map_err(|_| DimacsError::TooManySymbols { line: line_number, limit: MAX_INTERNED_BYTES })?;
+                names.insert(var, (line_number, symbol));
+            }
+            continue;
+        }
+        // SATLIB 벤치마크 파일은 '%' 줄 이후를 무시
+        if line.starts_with('%') {
+            break;
+        }
+        if line.starts_with('p') {
+            if header.is_some() {
+                return Err(DimacsError::InvalidHeader { line: line_number });
+            }
+            header = Some(parse_header(line).ok_or(DimacsError::InvalidHeader { line: line_number })?);
+            continue;
+        }
+
+        let (num_vars, _) = header.ok_or(DimacsError::MissingHeader)?;
+        for token in line.split_whitespace() {
+            let value: i64 = token.parse().map_err(|_| DimacsError::InvalidToken {
+                line: line_number,
+                token: token.to_string(),
+            })?;
+            if value == 0 {
+                clauses.push(std::mem::take(&mut current));
+                continue;
+            }
+            let var = value.unsigned_abs() as usize;
+            if var > num_vars {
+                return Err(DimacsError::VariableOutOfRange { line: line_number, var });
+            }
+            // 일단 DIMACS 번호 그대로 두고, 끝에서 나온 변수들만으로 번호를 다시 매김
+            current.push(Lit::new(var, value < 0));
+        }
+    }
+
+    let (num_vars, num_clauses) = header.ok_or(DimacsError::MissingHeader)?;
+    // 마지막 절이 0으로 끝나지 않은 경우도 허용
+    if !current.is_empty() {
+        clauses.push(current);
+    }
+    if clauses.len() != num_clauses {
+        return Err(DimacsError::ClauseCountMismatch { expected: num_clauses, found: clauses.len() });
+    }
+
+    let mut used: BTreeSet<usize> = clauses.iter().flatten().map(|lit| lit.var()).collect();
+    used.extend(names.keys().copied().filter(|&var| var <= num_vars));
+    let indices: HashMap<usize, usize> = used.iter().enumerate().map(|(index, &var)| (var, index)).collect();
+    for lit in clauses.iter_mut().flatten() {
+        *lit = Lit::new(indices[&lit.var()], lit.is_negated());
+    }
+    let variables = name_variables(&used, &names)?;
+    Ok(ClauseSet { variables, clauses })
+}
+
+// "c var 3 door" -> (3, "door")
+fn parse_name_comment(comment: &str) -> Option<(usize, String)> {
+    let mut parts = comment.split_whitespace();
+    if parts.next()? != "var" {
+        return None;
+    }
+    let var: usize = parts.next()?.parse().ok()?;
+    let name = unescape_name(parts.next()?)?;
+    if var == 0 || name.is_empty() || parts.next().is_some() {
+        return None;
+    }
+    Some((var, name))
+}
+
+// "p cnf 3 2" -> (3, 2). 리터럴로 표현할 수 없는 변수 개수는 거부
+fn parse_header(line: &str) -> Option<(usize, usize)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        ["p", "cnf", vars, clauses] => {
+            let vars: usize = vars.parse().ok()?;
+            (vars <= MAX_DIMACS_VARIABLES).then_some((vars, clauses.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+// 쓰인 변수마다 이름을 붙임. 변수 수는 입력 크기를 넘지 않음
+fn name_variables(vars: &BTreeSet<usize>, names: &HashMap<usize, (usize, Symbol)>) -> Result<Vec<Symbol>, DimacsError> {
+    let mut variables = Vec::with_capacity(vars.len());
+    let mut used = HashMap::new();
+    for &var in vars {
+        let (line, symbol) = match names.get(&var) {
+            Some(&(line, symbol)) => (line, symbol),
+            None => (0, Symbol::numbered(var as u32)),
+        };
+        if let Some(previous_line) = used.insert(symbol, line) {
+            let line = line.max(previous_line);
+            return Err(DimacsError::DuplicateVariableName { line, name: symbol.name().to_string() });
+        }
+        variables.push(symbol);
+    }
+    Ok(variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex05::negation_normal_form::nnf;
+    use crate::ex06::conjunctive_normal_form::cnf;
+    use crate::ex07::sat::{solve_clauses, SatResult};
+
+    #[test]
+    fn test_write_dimacs() {
+        let cnf_ast = cnf(&nnf(&parse_expr("AB|C!&").unwrap()));
+        assert_eq!(cnf_to_dimacs(&cnf_ast), "c var 1 A\nc var 2 B\nc var 3 C\np cnf 3 2\n1 2 0\n-3 0\n");
+
+        // 읽은 결과는 원래 절 집합과 같고, 다시 쓰면 같은 문자열
+        let clause_set = ClauseSet::from_expr(&parse_expr("[door]B!>[x1]|").unwrap());
+        let dimacs = to_dimacs(&clause_set);
+        assert_eq!(parse_dimacs(&dimacs), Ok(clause_set.clone()));
+        assert_eq!(to_dimacs(&parse_dimacs(&dimacs).unwrap()), dimacs);
+        assert!(solve_clauses(&parse_dimacs(&dimacs).unwrap()).unwrap().is_sat());
+
+        // 공백과 역슬래시가 들어간 이름도 그대로 돌아옴
+        let names = ["door open", "a\\b", "tab\there", "x\\u{20}"];
+        let clause_set = ClauseSet {
+            variables: names.iter().map(|&name| Symbol::intern(name)).collect(),
+            clauses: vec![vec![Lit::new(0, false), Lit::new(1, true)], vec![Lit::new(2, false), Lit::new(3, false)]],
+        };
+        let dimacs = to_dimacs(&clause_set);
+        assert!(dimacs.starts_with("c var 1 door\\u{20}open\nc var 2 a\\\\b\nc var 3 tab\\u{9}here\n"));
+        assert_eq!(parse_dimacs(&dimacs), Ok(clause_set));
+    }
+
+    #[test]
+    fn test_read_dimacs() {
+        let input = "c simple example\np cnf 3 3\n1 -2 0\n2 3\n0\n-1 0\n%\n0\n";
+        let clause_set = parse_dimacs(input).unwrap();
+        assert_eq!(clause_set.variables, vec![Symbol::from("x1"), Symbol::from("x2"), Symbol::from("x3")]);
+        assert_eq!(clause_set.clauses.len(), 3);
+        assert_eq!(clause_set.to_expr(), parse_expr("[x1][x2]!|[x2][x3]|[x1]!&&").unwrap());
+        assert!(solve_clauses(&clause_set).unwrap().is_sat());
+
+        let unsat = parse_dimacs("p cnf 1 2\n1 0\n-1 0\n").unwrap();
+        assert_eq!(solve_clauses(&unsat), Ok(SatResult::Unsat));
+
+        assert_eq!(parse_dimacs("1 2 0\n"), Err(DimacsError::MissingHeader));
+        assert_eq!(parse_dimacs("p cnf x 1\n"), Err(DimacsError::InvalidHeader { line: 1 }));
+        assert_eq!(parse_dimacs("p cnf 99999999999 0\n"), Err(DimacsError::InvalidHeader { line: 1 }));
+
+        // 헤더의 변수 개수는 믿지 않음: 쓰인 변수와 이름이 있는 변수만 만듦
+        assert_eq!(parse_dimacs("p cnf 2147483647 0\n"), Ok(ClauseSet::default()));
+        let sparse = parse_dimacs("c var 7 door\np cnf 2147483647 1\n5 -2147483647 0\n").unwrap();
+        assert_eq!(sparse.variables, vec![Symbol::from("x5"), Symbol::from("door"), Symbol::from("x2147483647")]);
+        assert_eq!(sparse.clauses, vec![vec![Lit::new(0, false), Lit::new(2, true)]]);
+        assert_eq!(parse_dimacs(&to_dimacs(&sparse)), Ok(sparse));
+        // 잘못된 이스케이프가 있는 이름 주석은 무시
+        assert_eq!(parse_dimacs("c var 1 a\\q\np cnf 1 1\n1 0\n").unwrap().variables, vec![Symbol::from("x1")]);
+        assert_eq!(parse_dimacs("c var 1 a\\u{20\np cnf 1 1\n1 0\n").unwrap().variables, vec![Symbol::from("x1")]);
+        assert_eq!(
+            parse_dimacs("p cnf 2 1\n1 a 0\n"),
+            Err(DimacsError::InvalidToken { line: 2, token: "a".to_string() })
+        );
+        assert_eq!(parse_dimacs("p cnf 2 1\n1 3 0\n"), Err(DimacsError::VariableOutOfRange { line: 2, var: 3 }));
+        assert_eq!(parse_dimacs("p cnf 2 2\n1 2 0\n"), Err(DimacsError::ClauseCountMismatch { expected: 2, found: 1 }));
+        assert_eq!(
+            parse_dimacs("c var 1 A\nc var 2 A\np cnf 2 0\n"),
+            Err(DimacsError::DuplicateVariableName { line: 2, name: "A".to_string() })
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{parse_formula, Expr, Notation, Op};
+use crate::ex03::error::FormulaError;
+use super::sat::{solve_ast, SatResult};
+use std::fmt;
+
+// 두 식의 관계를 진리표 대신 SAT로 확인
+// 성질이 성립하지 않는 경우는 반례 식이 충족 가능하다는 뜻이므로, 그 모델을 반례로 돌려준다
+
+// 검사 결과. 성립하지 않으면 반례가 되는 할당을 함께 반환
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Holds,
+    Counterexample(Assignment),
+}
+
+impl Check {
+    pub fn holds(&self) -> bool {
+        matches!(self, Check::Holds)
+    }
+
+    pub fn counterexample(&self) -> Option<&Assignment> {
+        match self {
+            Check::Holds => None,
+            Check::Counterexample(assignment) => Some(assignment),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Holds => write!(f, "HOLDS"),
+            Check::Counterexample(assignment) if assignment.is_empty() => write!(f, "COUNTEREXAMPLE"),
+            Check::Counterexample(assignment) => write!(f, "COUNTEREXAMPLE {}", assignment),
+        }
+    }
+}
+
+// 반례 식이 충족 불가능하면 성립
+fn check_unsat(counter: &Expr) -> Result<Check, FormulaError> {
+    Ok(match solve_ast(counter)? {
+        SatResult::Unsat => Check::Holds,
+        SatResult::Sat(model) => Check::Counterexample(model),
+    })
+}
+
+// 모든 입력에서 f와 g의 값이 같은지: f ^ g가 충족 불가능
+pub fn equivalent(f: &Expr, g: &Expr) -> Result<Check, FormulaError> {
+    check_unsat(&Expr::binary(Op::Xor, f.clone(), g.clone()))
+}
+
+// f가 참인 모든 입력에서 g도 참인지: f & !g가 충족 불가능
+pub fn entails(f: &Expr, g: &Expr) -> Result<Check, FormulaError> {
+    check_unsat(&Expr::and(f.clone(), Expr::negate(g.clone())))
+}
+
+// 항상 참인지: !f가 충족 불가능
+pub fn is_tautology(f: &Expr) -> Result<Check, FormulaError> {
+    check_unsat(&Expr::negate(f.clone()))
+}
+
+// 항상 거짓인지: f가 충족 불가능. 반례는 f를 참으로 만드는 할당
+pub fn is_contradiction(f: &Expr) -> Result<Check, FormulaError> {
+    check_unsat(f)
+}
+
+/// 주어진 표기법으로 두 식을 읽어 동치인지 확인
+pub fn try_equivalent(f: &str, g: &str, notation: Notation) -> Result<Check, FormulaError> {
+    equivalent(&parse_formula(f, notation)?, &parse_formula(g, notation)?)
+}
+
+/// 주어진 표기법으로 두 식을 읽어 f가 g를 함의하는지 확인
+pub fn try_entails(f: &str, g: &str, notation: Notation) -> Result<Check, FormulaError> {
+    entails(&parse_formula(f, notation)?, &parse_formula(g, notation)?)
+}
+
+pub fn try_is_tautology(formula: &str, notation: Notation) -> Result<Check, FormulaError> {
+    is_tautology(&parse_formula(formula, notation)?)
+}
+
+pub fn try_is_contradiction(formula: &str, notation: Notation) -> Result<Check, FormulaError> {
+    is_contradiction(&parse_formula(formula, notation)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex03::boolean_evaluation::eval_with;
+
+    #[test]
+    fn test_equivalent() {
+        let postfix = |f: &str, g: &str| try_equivalent(f, g, Notation::Postfix).unwrap();
+        assert!(postfix("AB&!", "A!B!|").holds());
+        assert!(postfix("AB>", "A!B|").holds());
+        assert!(postfix("AB=", "AB^!").holds());
+        assert!(postfix("AB|C&", "AC&BC&|").holds());
+        assert!(try_equivalent("a & (b | c)", "a & b | a & c", Notation::Infix).unwrap().holds());
+
+        // 반례에서는 두 식의 값이 다름
+        for (f, g) in [("AB>", "BA>"), ("AB|", "AB^"), ("AB&C|", "AB|C&"), ("A", "B")] {
+            let check = postfix(f, g);
+            let counterexample = check.counterexample().expect(f).clone();
+            let (f, g) = (parse_expr(f).unwrap(), parse_expr(g).unwrap());
+            assert_ne!(eval_with(&f, &counterexample), eval_with(&g, &counterexample));
+        }
+        assert_eq!(postfix("A", "A!"), Check::Counterexample(Assignment::new().with('A', false)));
+        assert_eq!(postfix("A", "A!").to_string(), "COUNTEREXAMPLE A=0");
+        assert_eq!(postfix("1", "0").to_string(), "COUNTEREXAMPLE");
+        assert_eq!(postfix("A", "AA&").to_string(), "HOLDS");
+        assert_eq!(
+            try_equivalent("A&", "A", Notation::Postfix),
+            Err(FormulaError::StackUnderflow { token: 1, operator: '&' })
+        );
+    }
+
+    #[test]
+    fn test_entails_and_validity() {
+        let entails_postfix = |f: &str, g: &str| try_entails(f, g, Notation::Postfix).unwrap();
+        assert!(entails_postfix("AB&", "A").holds());
+        assert!(entails_postfix("AAB>&", "B").holds()); // modus ponens
+        assert!(entails_postfix("0", "A").holds());
+        let check = entails_postfix("AB|", "A");
+        let counterexample = check.counterexample().unwrap();
+        assert_eq!(counterexample.get('A'), Some(false));
+        assert_eq!(counterexample.get('B'), Some(true));
+
+        let tautology = |formula: &str| try_is_tautology(formula, Notation::Postfix).unwrap();
+        assert!(tautology("AA!|").holds());
+        assert!(tautology("AB>A>A>").holds()); // Peirce의 법칙
+        assert!(tautology("1").holds());
+        assert_eq!(tautology("AB>"), Check::Counterexample(Assignment::new().with('A', true).with('B', false)));
+
+        let contradiction = |formula: &str| try_is_contradiction(formula, Notation::Postfix).unwrap();
+        assert!(contradiction("AA!&").holds());
+        assert!(contradiction("AB^AB=&").holds());
+        assert!(try_is_contradiction("x & !x", Notation::Infix).unwrap().holds());
+        let check = contradiction("AB!&");
+        assert_eq!(check.counterexample(), Some(&Assignment::new().with('A', true).with('B', false)));
+    }
+}
@@ -1 +1,6 @@
-pub mod sat;
\ No newline at end of file
+pub mod clauses;
+pub mod solver;
+pub mod dimacs;
+pub mod sat;
+pub mod equivalence;
+pub mod model_counting;
\ No newline at end of file
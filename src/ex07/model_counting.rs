@@ -0,0 +1,349 @@
+use std::collections::{HashMap, HashSet};
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{parse_formula, Expr, Notation};
+use crate::ex03::boolean_evaluation::eval_with;
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::{evaluate_truth_table, extract_and_sort_vars_from_ast};
+use crate::ex06::conjunctive_normal_form::CnfMode;
+use crate::ex06::tseitin::tseitin;
+use super::clauses::{Clause, ClauseSet, Lit};
+use super::solver::Solver;
+
+// 모델 수 세기(#SAT)와 모든 해 나열
+// - 변수가 적으면 진리표를 그대로 셈
+// - 그 외에는 Tseitin 인코딩을 component caching DPLL로 셈
+//   (양방향 절을 쓰는 Tseitin에서는 보조 변수가 원래 변수로 결정되므로 모델 수가 같음)
+
+// 이 이하의 변수 수는 진리표로 셈
+pub const TRUTH_TABLE_LIMIT: usize = 12;
+// u128에 2^n이 들어가는 최대 변수 수
+pub const MAX_COUNT_VARIABLES: usize = 127;
+
+// 식을 참으로 만드는 할당의 수 (식에 나타나는 변수 기준)
+pub fn count_models_ast(ast: &Expr) -> Result<u128, FormulaError> {
+    let vars = extract_and_sort_vars_from_ast(ast);
+    if vars.len() > MAX_COUNT_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_COUNT_VARIABLES });
+    }
+    if vars.len() <= TRUTH_TABLE_LIMIT {
+        let results = evaluate_truth_table(ast, &vars)?;
+        return Ok(results.iter().filter(|&&result| result).count() as u128);
+    }
+    Ok(count_clauses(&ClauseSet::from_encoding(ast, &tseitin(ast))))
+}
+
+// 절 집합의 모델 수 (절에 나타나지 않는 변수도 포함하여 모든 변수 기준)
+pub fn count_clause_models(clause_set: &ClauseSet) -> Result<u128, FormulaError> {
+    if clause_set.num_vars() > MAX_COUNT_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: clause_set.num_vars(), limit: MAX_COUNT_VARIABLES });
+    }
+    Ok(count_clauses(clause_set))
+}
+
+// 보조 변수가 원래 변수로 결정되는 인코딩은 전체 변수 수가 한도를 넘어도 모델 수는 넘지 않음
+fn count_clauses(clause_set: &ClauseSet) -> u128 {
+    let mut clauses: Vec<Clause> = Vec::new();
+    for clause in &clause_set.clauses {
+        let mut clause = clause.clone();
+        clause.sort();
+        clause.dedup();
+        // 항진 절(x | !x)은 제약이 없으므로 제외
+        if clause.windows(2).any(|pair| pair[0] == !pair[1]) {
+            continue;
+        }
+        clauses.push(clause);
+    }
+    clauses.sort();
+    clauses.dedup();
+    let mut counter = Counter { cache: HashMap::new() };
+    counter.count(clauses, clause_set.num_vars())
+}
+
+struct Counter {
+    cache: HashMap<Vec<Clause>, u128>, // 컴포넌트(정렬된 절 목록) -> 모델 수
+}
+
+impl Counter {
+    // 아직 값이 정해지지 않은 변수 scope개에 대한 모델 수
+    fn count(&mut self, clauses: Vec<Clause>, scope: usize) -> u128 {
+        let (clauses, assigned) = match propagate(clauses) {
+            Some(result) => result,
+            None => return 0,
+        };
+        let appearing = variables_of(&clauses).len();
+        let free = scope - assigned - appearing;
+        let mut total = 1u128 << free;
+        for component in components(clauses) {
+            let count = self.count_component(component);
+            if count == 0 {
+                return 0;
+            }
+            total *= count;
+        }
+        total
+    }
+
+    // 변수를 공유하지 않는 절 묶음 하나. 가장 자주 나타나는 변수로 나누어 셈
+    fn count_component(&mut self, component: Vec<Clause>) -> u128 {
+        if let Some(&count) = self.cache.get(&component) {
+            return count;
+        }
+        let mut occurrences: HashMap<usize, usize> = HashMap::new();
+        for lit in component.iter().flatten() {
+            *occurrences.entry(lit.var()).or_default() += 1;
+        }
+        let scope = occurrences.len();
+        let (&var, _) = occurrences
+            .iter()
+            .max_by_key(|&(&var, &count)| (count, std::cmp::Reverse(var)))
+            .expect("a component has at least one variable");
+        let count = self.count(assign(&component, Lit::new(var, false)), scope - 1)
+            + self.count(assign(&component, Lit::new(var, true)), scope - 1);
+        self.cache.insert(component, count);
+        count
+    }
+}
+
+// lit을 참으로 둔 절 목록: lit을 포함하는 절은 지우고 !lit은 절에서 뺌
+fn assign(clauses: &[Clause], lit: Lit) -> Vec<Clause> {
+    clauses
+        .iter()
+        .filter(|clause| !clause.contains(&lit))
+        .map(|clause| clause.iter().copied().filter(|&other| other != !lit).collect())
+        .collect()
+}
+
+// 단위 전파. 모순이면 None, 아니면 남은 절과 값이 정해진 변수 수
+fn propagate(mut clauses: Vec<Clause>) -> Option<(Vec<Clause>, usize)> {
+    let mut assigned = 0;
+    loop {
+        if clauses.iter().any(|clause| clause.is_empty()) {
+            return None;
+        }
+        match clauses.iter().find(|clause| clause.len() == 1) {
+            Some(unit) => {
+                let lit = unit[0];
+                clauses = assign(&clauses, lit);
+                assigned += 1;
+            }
+            None => return Some((clauses, assigned)),
+        }
+    }
+}
+
+fn variables_of(clauses: &[Clause]) -> HashSet<usize> {
+    clauses.iter().flatten().map(|lit| lit.var()).collect()
+}
+
+// 변수를 공유하는 절끼리 묶음 (union-find). 각 컴포넌트는 캐시 키로 쓰도록 정렬
+fn components(clauses: Vec<Clause>) -> Vec<Vec<Clause>> {
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+    fn find(parent: &mut HashMap<usize, usize>, var: usize) -> usize {
+        let next = *parent.entry(var).or_insert(var);
+        if next == var {
+            return var;
+        }
+        let root = find(parent, next);
+        parent.insert(var, root);
+        root
+    }
+    for clause in &clauses {
+        let first = find(&mut parent, clause[0].var());
+        for lit in &clause[1..] {
+            let root = find(&mut parent, lit.var());
+            parent.insert(root, first);
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<Clause>> = HashMap::new();
+    for clause in clauses {
+        let root = find(&mut parent, clause[0].var());
+        groups.entry(root).or_default().push(clause);
+    }
+    let mut components: Vec<Vec<Clause>> = groups
+        .into_values()
+        .map(|mut component| {
+            component.sort();
+            component
+        })
+        .collect();
+    components.sort();
+    components
+}
+
+// 모델 수를 세고, 실패하면 0을 반환하는 함수
+pub fn count_models(formula: &str) -> u128 {
+    match try_count_models(formula) {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("Invalid formula: {}", e);
+            0
+        }
+    }
+}
+
+pub fn try_count_models(formula: &str) -> Result<u128, FormulaError> {
+    try_count_models_with_notation(formula, Notation::Postfix)
+}
+
+pub fn try_count_models_with_notation(formula: &str, notation: Notation) -> Result<u128, FormulaError> {
+    count_models_ast(&parse_formula(formula, notation)?)
+}
+
+// 모든 모델을 차례로 반환하는 반복자
+// 모델을 찾을 때마다 투영 변수에 대한 차단 절(blocking clause)을 추가하여 같은 투영이 다시 나오지 않게 함
+pub struct AllModels {
+    ast: Expr,
+    complete: bool, // 식의 모든 변수가 투영에 들어 있으면 디버그 빌드에서 원래 식으로 모델을 검증
+    solver: Solver,
+    projection: Vec<(Symbol, usize)>, // 투영 변수와 절 집합에서의 변수 번호
+    done: bool,
+}
+
+impl Iterator for AllModels {
+    type Item = Assignment;
+
+    fn next(&mut self) -> Option<Assignment> {
+        if self.done {
+            return None;
+        }
+        let values = match self.solver.solve() {
+            Some(values) => values,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let model: Assignment = values_to_assignment(&self.projection, &values);
+        if self.complete {
+            debug_assert_eq!(eval_with(&self.ast, &model), Ok(true), "enumerated a model that does not satisfy the formula");
+        }
+        let blocking: Clause = self.projection.iter().map(|&(_, index)| Lit::new(index, values[index])).collect();
+        if !self.solver.add_clause(&blocking) {
+            self.done = true;
+        }
+        Some(model)
+    }
+}
+
+fn values_to_assignment(projection: &[(Symbol, usize)], values: &[bool]) -> Assignment {
+    projection.iter().map(|&(var, index)| (var, values[index])).collect()
+}
+
+// 식의 모든 변수에 대한 모델
+pub fn all_models(ast: &Expr) -> AllModels {
+    all_models_projected(ast, &extract_and_sort_vars_from_ast(ast))
+}
+
+// 주어진 변수들로 투영한 모델. 나머지 변수는 식을 참으로 만드는 값이 있기만 하면 됨
+// 식에 없는 변수는 어느 값이든 되므로 두 값 모두 나옴
+pub fn all_models_projected(ast: &Expr, vars: &[Symbol]) -> AllModels {
+    let mut clause_set = ClauseSet::from_expr_with_mode(ast, CnfMode::Equisatisfiable);
+    let mut indices = clause_set.var_indices();
+    let mut projection = Vec::new();
+    for &var in vars {
+        let index = *indices.entry(var).or_insert_with(|| {
+            clause_set.variables.push(var);
+            clause_set.variables.len() - 1
+        });
+        if !projection.iter().any(|&(_, other)| other == index) {
+            projection.push((var, index));
+        }
+    }
+    let complete = extract_and_sort_vars_from_ast(ast).iter().all(|var| vars.contains(var));
+    let solver = Solver::from_clause_set(&clause_set);
+    AllModels { ast: ast.clone(), complete, solver, projection, done: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+    use crate::ex03::ast::Op;
+    use crate::ex07::dimacs::parse_dimacs;
+
+    fn var(name: String) -> Expr {
+        Expr::Var(Symbol::intern(&name))
+    }
+
+    #[test]
+    fn test_count_models() {
+        assert_eq!(count_models("A"), 1);
+        assert_eq!(count_models("AB|"), 3);
+        assert_eq!(count_models("AB^"), 2);
+        assert_eq!(count_models("AA!&"), 0);
+        assert_eq!(count_models("1"), 1);
+        assert_eq!(count_models("0"), 0);
+        assert_eq!(count_models("ABC||"), 7);
+        assert_eq!(try_count_models_with_notation("a & b | c", Notation::Infix), Ok(5));
+        assert_eq!(try_count_models("A&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+
+        // 진리표와 DPLL 결과가 같음
+        for formula in ["AB>C^", "AB=C=D|", "ABCDE^^^^", "AB|A!B!|&", "AB&CD&|EF&|A!&", "A1|", "A0&B|"] {
+            let ast = parse_expr(formula).unwrap();
+            let vars = extract_and_sort_vars_from_ast(&ast);
+            let by_table = count_models_ast(&ast).unwrap();
+            let by_dpll = count_clauses(&ClauseSet::from_encoding(&ast, &tseitin(&ast)));
+            assert_eq!(by_table, by_dpll, "{}", formula);
+            let by_table = evaluate_truth_table(&ast, &vars).unwrap().into_iter().filter(|&r| r).count();
+            assert_eq!(by_dpll, by_table as u128, "{}", formula);
+        }
+
+        // 100개 변수: 독립인 (a_i | b_i) 50개 -> 3^50 (컴포넌트 분해로 바로 셈)
+        let mut ast = Expr::Const(true);
+        for i in 0..50 {
+            ast = Expr::and(ast, Expr::or(var(format!("a{}", i)), var(format!("b{}", i))));
+        }
+        assert_eq!(count_models_ast(&ast), Ok(3u128.pow(50)));
+
+        // 연결된 사슬: x_i -> x_(i+1) 를 만족하는 할당은 n + 1개
+        let mut ast = Expr::Const(true);
+        for i in 0..40 {
+            ast = Expr::and(ast, Expr::binary(Op::Implies, var(format!("x{}", i)), var(format!("x{}", i + 1))));
+        }
+        assert_eq!(count_models_ast(&ast), Ok(42));
+
+        // DIMACS에서 읽은 절 집합: 헤더에만 있는 변수 x3은 세지 않지만 이름 주석이 있는 변수는 셈
+        let clause_set = parse_dimacs("p cnf 3 2\n1 2 0\n-1 0\n").unwrap();
+        assert_eq!(count_clause_models(&clause_set), Ok(1));
+        let clause_set = parse_dimacs("c var 3 C\np cnf 3 2\n1 2 0\n-1 0\n").unwrap();
+        assert_eq!(count_clause_models(&clause_set), Ok(2));
+        let wide: Vec<String> = (1..=128).map(|var| var.to_string()).collect();
+        let clause_set = parse_dimacs(&format!("p cnf 128 1\n{} 0\n", wide.join(" "))).unwrap();
+        assert_eq!(count_clause_models(&clause_set), Err(FormulaError::TooManyVariables { count: 128, limit: 127 }));
+
+        let wide: Vec<Expr> = (0..128).map(|i| var(format!("w{}", i))).collect();
+        let ast = wide.into_iter().reduce(Expr::or).unwrap();
+        assert_eq!(count_models_ast(&ast), Err(FormulaError::TooManyVariables { count: 128, limit: 127 }));
+    }
+
+    #[test]
+    fn test_all_models() {
+        let ast = parse_expr("AB|").unwrap();
+        let mut models: Vec<String> = all_models(&ast).map(|model| model.to_string()).collect();
+        models.sort();
+        assert_eq!(models, vec!["A=0 B=1", "A=1 B=0", "A=1 B=1"]);
+        assert_eq!(all_models(&parse_expr("AA!&").unwrap()).count(), 0);
+        assert_eq!(all_models(&parse_expr("1").unwrap()).collect::<Vec<_>>(), vec![Assignment::new()]);
+
+        // 모델 수와 나열한 모델 수가 같고, 중복이 없음
+        for formula in ["AB>C^", "AB=C=D|", "ABCDE^^^^", "AB&CD&|EF&|A!&"] {
+            let ast = parse_expr(formula).unwrap();
+            let models: Vec<Assignment> = all_models(&ast).collect();
+            assert_eq!(models.len() as u128, count_models_ast(&ast).unwrap(), "{}", formula);
+            let distinct: HashSet<String> = models.iter().map(|model| model.to_string()).collect();
+            assert_eq!(distinct.len(), models.len(), "{}", formula);
+        }
+
+        // 투영: (A & B) | (A & C)를 A로 투영하면 A=1 하나
+        let ast = parse_expr("AB&AC&|").unwrap();
+        let projected: Vec<String> = all_models_projected(&ast, &[Symbol::from('A')]).map(|m| m.to_string()).collect();
+        assert_eq!(projected, vec!["A=1"]);
+        let projected = all_models_projected(&ast, &[Symbol::from('B'), Symbol::from('C')]).count();
+        assert_eq!(projected, 3);
+        // 식에 없는 변수는 두 값 모두
+        assert_eq!(all_models_projected(&parse_expr("A").unwrap(), &[Symbol::from('A'), Symbol::from('Z')]).count(), 2);
+        assert_eq!(all_models_projected(&ast, &[]).count(), 1);
+    }
+}
@@ -1,69 +1,152 @@
-use crate::ex03::ast::{ASTNode, get_ast};
-use crate::ex05::negation_normal_form::nnf;
-use crate::ex06::conjunctive_normal_form::cnf;
-use crate::ex04::truth_table::extract_variables;
-use std::collections::{HashMap, HashSet};
+use crate::ex03::assignment::Assignment;
+use crate::ex03::ast::{Expr, Notation, parse_formula};
+use crate::ex03::boolean_evaluation::eval_with;
+use crate::ex03::error::FormulaError;
+use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+use crate::ex06::conjunctive_normal_form::CnfMode;
+use super::clauses::ClauseSet;
+use super::solver::Solver;
+use std::fmt;
 
-// SAT 문제를 해결하는 함수
-pub fn sat(formula: &str) -> bool {
-    let ast = match get_ast(formula) {
-        Ok(ast) => ast,
-        Err(_) => {
-            eprintln!("Invalid formula");
-            return false;
+// SAT 풀이 결과. 충족 가능하면 만족하는 할당(모델)을 함께 반환
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SatResult {
+    Sat(Assignment),
+    Unsat,
+}
+
+impl SatResult {
+    pub fn is_sat(&self) -> bool {
+        matches!(self, SatResult::Sat(_))
+    }
+
+    pub fn model(&self) -> Option<&Assignment> {
+        match self {
+            SatResult::Sat(model) => Some(model),
+            SatResult::Unsat => None,
         }
-    };
+    }
 
-    let nnf_ast = nnf(&ast);
-    let cnf_ast = cnf(&nnf_ast);
-
-    // 변수 추출
-    let mut variables = HashSet::new();
-    extract_variables(&cnf_ast, &mut variables);
-    
-    // 가능한 모든 변수 할당 조합을 테스트
-    let var_list: Vec<char> = variables.into_iter().collect();
-    let num_combinations = 1 << var_list.len();  // 2^n 조합
-
-    for i in 0..num_combinations {
-        let mut assignments = HashMap::new();
-        
-        // i 값에 따라 변수들의 참/거짓 값을 설정
-        for (j, &var) in var_list.iter().enumerate() {
-            let value = (i & (1 << j)) != 0;
-            assignments.insert(var, value);
+    // 모델을 진리표와 같은 열 순서로 출력하는 문자열
+    // | A | B |
+    // |---|---|
+    // | 1 | 0 |
+    pub fn to_table(&self) -> String {
+        let model = match self {
+            SatResult::Sat(model) => model,
+            SatResult::Unsat => return "UNSAT\n".to_string(),
+        };
+        let mut header = String::from("|");
+        let mut separator = String::from("|");
+        let mut row = String::from("|");
+        for (var, value) in model.iter() {
+            let width = var.name().chars().count();
+            header.push_str(&format!(" {} |", var));
+            separator.push_str(&format!("{}|", "-".repeat(width + 2)));
+            row.push_str(&format!(" {:^width$} |", if value { 1 } else { 0 }, width = width));
         }
+        format!("{}\n{}\n{}\n", header, separator, row)
+    }
+}
 
-        // 할당된 변수들로 CNF 평가
-        if solve_cnf(&cnf_ast, &assignments) {
-            return true; // 참인 조합을 찾으면 true 반환
+impl fmt::Display for SatResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SatResult::Sat(model) if model.is_empty() => write!(f, "SAT"),
+            SatResult::Sat(model) => write!(f, "SAT {}", model),
+            SatResult::Unsat => write!(f, "UNSAT"),
         }
     }
+}
 
-    false // 모든 조합을 시도해도 참인 조합이 없으면 false 반환
+// SAT 문제를 해결하는 함수
+// 기존 동작을 유지하여 식이 잘못되면 에러를 출력하고 false를 반환. 구별하려면 try_sat 사용
+pub fn sat(formula: &str) -> bool {
+    sat_with_notation(formula, Notation::Postfix)
 }
 
-// CNF 논리식을 주어진 변수 할당에 따라 평가하는 함수
-fn solve_cnf(ast: &ASTNode, assignments: &HashMap<char, bool>) -> bool {
-    match ast {
-        ASTNode::Operand(var) => {
-            *assignments.get(var).unwrap_or(&false)  // 변수 값 참조, 기본은 false
-        }
-        ASTNode::Operator('&', left, Some(right)) => {
-            solve_cnf(left, assignments) && solve_cnf(right, assignments)
-        }
-        ASTNode::Operator('|', left, Some(right)) => {
-            solve_cnf(left, assignments) || solve_cnf(right, assignments)
+// 주어진 표기법으로 작성된 논리식의 SAT 문제를 해결하는 함수
+pub fn sat_with_notation(formula: &str, notation: Notation) -> bool {
+    match try_sat_with_notation(formula, notation) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Invalid formula: {}", e);
+            false
         }
-        ASTNode::Operator('!', operand, None) => {
-            !solve_cnf(operand, assignments)
-        }
-        _ => false,
     }
 }
 
+// SAT 문제를 해결하고, 실패하면 에러를 반환하는 함수
+pub fn try_sat(formula: &str) -> Result<bool, FormulaError> {
+    try_sat_with_notation(formula, Notation::Postfix)
+}
+
+pub fn try_sat_with_notation(formula: &str, notation: Notation) -> Result<bool, FormulaError> {
+    Ok(try_solve_with_notation(formula, notation)?.is_sat())
+}
+
+// 만족하는 할당까지 찾아 반환하는 함수. 식이 잘못되면 eval_formula처럼 패닉
+pub fn solve(formula: &str) -> SatResult {
+    solve_with_notation(formula, Notation::Postfix)
+}
+
+pub fn solve_with_notation(formula: &str, notation: Notation) -> SatResult {
+    try_solve_with_notation(formula, notation).unwrap_or_else(|e| panic!("Failed to solve formula: {}", e))
+}
+
+pub fn try_solve(formula: &str) -> Result<SatResult, FormulaError> {
+    try_solve_with_notation(formula, Notation::Postfix)
+}
+
+pub fn try_solve_with_notation(formula: &str, notation: Notation) -> Result<SatResult, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    solve_ast(&ast)
+}
+
+// 선형 크기의 CNF 인코딩을 CDCL 솔버로 풀고, 찾은 모델을 원래 AST로 다시 검증
+pub fn solve_ast(ast: &Expr) -> Result<SatResult, FormulaError> {
+    solve_ast_with_mode(ast, CnfMode::Equisatisfiable)
+}
+
+pub fn solve_ast_with_mode(ast: &Expr, mode: CnfMode) -> Result<SatResult, FormulaError> {
+    let clause_set = ClauseSet::from_expr_with_mode(ast, mode);
+    let model = match solve_clauses(&clause_set)? {
+        SatResult::Sat(model) => model,
+        SatResult::Unsat => return Ok(SatResult::Unsat),
+    };
+
+    // 보조 변수는 빼고 원래 식의 변수만 남김
+    let variables = extract_and_sort_vars_from_ast(ast);
+    let model: Assignment = model.iter().filter(|(var, _)| variables.contains(var)).collect();
+    if !verify_model(ast, &model)? {
+        return Err(FormulaError::InvalidModel(model.to_string()));
+    }
+    Ok(SatResult::Sat(model))
+}
+
+// 절 집합(예: DIMACS에서 읽은 문제)을 직접 푸는 함수
+// 솔버의 모델이 절을 위반하면 InvalidModel
+pub fn solve_clauses(clause_set: &ClauseSet) -> Result<SatResult, FormulaError> {
+    let mut solver = Solver::from_clause_set(clause_set);
+    let values = match solver.solve() {
+        Some(values) => values,
+        None => return Ok(SatResult::Unsat),
+    };
+    let model: Assignment = clause_set.variables.iter().copied().zip(values.iter().copied()).collect();
+    if !clause_set.is_satisfied_by(&values) {
+        return Err(FormulaError::InvalidModel(model.to_string()));
+    }
+    Ok(SatResult::Sat(model))
+}
+
+// 변환 전의 원래 AST를 모델로 평가하여 모델이 올바른지 확인하는 함수
+pub fn verify_model(ast: &Expr, model: &Assignment) -> Result<bool, FormulaError> {
+    eval_with(ast, model)
+}
+
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -88,6 +171,66 @@ mod tests {
 
         // 추가 테스트 케이스
         assert_eq!(sat("AB|"), true);               // A | B는 참 (하나라도 참일 경우)
+        assert_eq!(sat("0"), false);                // 상수 0은 거짓
+        assert_eq!(sat("A0&"), false);              // A & 0은 거짓
+        assert_eq!(sat("A!1|"), true);              // !A | 1은 참
+    }
+
+    #[test]
+    fn test_sat_infix() {
+        assert_eq!(sat_with_notation("A & !A", Notation::Infix), false);
+        assert_eq!(sat_with_notation("A ∧ ¬B", Notation::Infix), true);
+        assert_eq!(sat_with_notation("(A | B) & !A & !B", Notation::Infix), false);
+        assert_eq!(sat_with_notation("A ⊕ A", Notation::Infix), false);
+        assert_eq!(sat_with_notation("door_open & !door_open", Notation::Infix), false);
+        assert_eq!(sat_with_notation("x1 & !x10", Notation::Infix), true);
+    }
+
+    #[test]
+    fn test_try_sat() {
+        assert_eq!(try_sat("AB|"), Ok(true));
+        assert_eq!(try_sat("AA!&"), Ok(false));
+        assert_eq!(try_sat("AB|&"), Err(FormulaError::StackUnderflow { token: 3, operator: '&' }));
+        assert_eq!(try_sat("A?"), Err(FormulaError::UnexpectedCharacter { ch: '?', offset: 1 }));
+        assert_eq!(sat("A?"), false);
+    }
+
+    #[test]
+    fn test_solve() {
+        let result = solve("AB&C!&");
+        assert_eq!(result.to_string(), "SAT A=1 B=1 C=0");
+        assert_eq!(result.to_table(), "| A | B | C |\n|---|---|---|\n| 1 | 1 | 0 |\n");
+        assert_eq!(solve("AA!&"), SatResult::Unsat);
+        assert_eq!(solve("AA!&").to_string(), "UNSAT");
+        assert_eq!(solve("1"), SatResult::Sat(Assignment::new()));
+
+        // 모델은 항상 원래 식을 만족해야 함
+        for formula in ["AB>C^", "AB=C=D|", "ABCDE^^^^", "AB|A!B!|&", "AB>A>A>"] {
+            let ast = parse_formula(formula, Notation::Postfix).unwrap();
+            let model = solve(formula).model().cloned().unwrap();
+            assert_eq!(verify_model(&ast, &model), Ok(true));
+        }
+
+        // 진리표와 같은 열 순서
+        let result = solve_with_notation("door & !x1", Notation::Infix);
+        assert_eq!(result.to_table(), "| door | x1 |\n|------|----|\n|  1   | 0  |\n");
+        // 두 CNF 방식은 같은 결과를 냄
+        for formula in ["AB&CD&|EF&|", "AB^C^A!&", "AB=B!A=&", "AB>BC>&AC>!&"] {
+            let ast = parse_formula(formula, Notation::Postfix).unwrap();
+            let linear = solve_ast_with_mode(&ast, CnfMode::Equisatisfiable).unwrap();
+            let equivalent = solve_ast_with_mode(&ast, CnfMode::Equivalent).unwrap();
+            assert_eq!(linear.is_sat(), equivalent.is_sat(), "{}", formula);
+            if let Some(model) = linear.model() {
+                assert_eq!(model.variables().count(), extract_and_sort_vars_from_ast(&ast).len());
+            }
+        }
+        assert_eq!(try_solve("A&"), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to solve formula")]
+    fn test_solve_invalid_formula() {
+        solve("A&");
     }
 }
 
@@ -0,0 +1,704 @@
+use super::clauses::{ClauseSet, Lit};
+
+// CDCL SAT 솔버
+// - 두 개의 감시 리터럴(two watched literals)을 이용한 단위 전파
+// - 1-UIP 충돌 분석과 절 학습
+// - VSIDS 변수 활동도 기반 분기 + 위상 저장(phase saving)
+// - Luby 수열 기반 재시작
+// - 활동도가 낮은 학습 절 삭제
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Value {
+    True,
+    False,
+    Unassigned,
+}
+
+struct ClauseData {
+    lits: Vec<Lit>,
+    learnt: bool,
+    activity: f64,
+}
+
+pub struct Solver {
+    num_vars: usize,
+    clauses: Vec<ClauseData>,
+    watches: Vec<Vec<usize>>, // 리터럴 코드 -> 그 리터럴을 감시하는 절 번호
+    values: Vec<Value>,       // 변수별 현재 값
+    levels: Vec<usize>,       // 변수가 할당된 결정 레벨
+    reasons: Vec<Option<usize>>, // 변수를 함의한 절
+    trail: Vec<Lit>,
+    trail_limits: Vec<usize>, // 각 결정 레벨이 시작되는 trail 위치
+    propagate_head: usize,
+    activity: Vec<f64>,
+    var_increment: f64,
+    clause_increment: f64,
+    order: VarHeap,
+    saved_phase: Vec<bool>,
+    seen: Vec<bool>,
+    max_learnts: f64,
+    num_learnts: usize,
+    unsat: bool, // 레벨 0에서 이미 모순이 발견됨
+    pub conflicts: u64,
+    pub decisions: u64,
+    pub propagations: u64,
+}
+
+const VAR_DECAY: f64 = 0.95;
+const CLAUSE_DECAY: f64 = 0.999;
+const RESTART_BASE: u64 = 100;
+
+impl Solver {
+    pub fn new(num_vars: usize) -> Solver {
+        let activity = vec![0.0; num_vars];
+        let mut order = VarHeap::new(num_vars);
+        for var in 0..num_vars {
+            order.insert(var, &activity);
+        }
+        Solver {
+            num_vars,
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * num_vars],
+            values: vec![Value::Unassigned; num_vars],
+            levels: vec![0; num_vars],
+            reasons: vec![None; num_vars],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagate_head: 0,
+            activity,
+            var_increment: 1.0,
+            clause_increment: 1.0,
+            order,
+            saved_phase: vec![false; num_vars],
+            seen: vec![false; num_vars],
+            max_learnts: 0.0,
+            num_learnts: 0,
+            unsat: false,
+            conflicts: 0,
+            decisions: 0,
+            propagations: 0,
+        }
+    }
+
+    pub fn from_clause_set(clause_set: &ClauseSet) -> Solver {
+        let mut solver = Solver::new(clause_set.num_vars());
+        for clause in &clause_set.clauses {
+            solver.add_clause(clause);
+        }
+        solver
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    // 절을 추가. 레벨 0에서 모순이 생기면 false 반환
+    pub fn add_clause(&mut self, clause: &[Lit]) -> bool {
+        if self.unsat {
+            return false;
+        }
+        self.backtrack(0);
+
+        // 중복 리터럴 제거, 항진 절과 이미 참인 절은 무시, 거짓 리터럴은 제거
+        let mut lits: Vec<Lit> = clause.to_vec();
+        lits.sort();
+        lits.dedup();
+        let mut simplified = Vec::with_capacity(lits.len());
+        for (i, &lit) in lits.iter().enumerate() {
+            if i > 0 && lits[i - 1] == !lit {
+                return true;
+            }
+            match self.lit_value(lit) {
+                Value::True => return true,
+                Value::False => {}
+                Value::Unassigned => simplified.push(lit),
+            }
+        }
+
+        match simplified.len() {
+            0 => {
+                self.unsat = true;
+                false
+            }
+            1 => {
+                self.assign(simplified[0], None);
+                if self.propagate().is_some() {
+                    self.unsat = true;
+                    return false;
+                }
+                true
+            }
+            _ => {
+                self.attach_clause(simplified, false);
+                true
+            }
+        }
+    }
+
+    // 만족하는 할당을 찾으면 변수별 값을, 충족 불가능하면 None을 반환
+    pub fn solve(&mut self) -> Option<Vec<bool>> {
+        if self.unsat {
+            return None;
+        }
+        self.max_learnts = (self.clauses.len() as f64 / 3.0).max(100.0);
+        let mut restart_count = 0;
+        loop {
+            let budget = luby(restart_count) * RESTART_BASE;
+            match self.search(budget) {
+                Some(true) => {
+                    let model = self
+                        .values
+                        .iter()
+                        .map(|&value| value == Value::True)
+                        .collect();
+                    self.backtrack(0);
+                    return Some(model);
+                }
+                Some(false) => {
+                    self.unsat = true;
+                    return None;
+                }
+                None => restart_count += 1, // 재시작
+            }
+        }
+    }
+
+    // 충돌 횟수 예산 안에서 탐색. 결론이 나지 않으면 None
+    fn search(&mut self, conflict_budget: u64) -> Option<bool> {
+        let mut conflicts_here = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                self.conflicts += 1;
+                conflicts_here += 1;
+                if self.decision_level() == 0 {
+                    return Some(false);
+                }
+                let (learnt, backtrack_level) = self.analyze(conflict);
+                self.backtrack(backtrack_level);
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let index = self.attach_clause(learnt, true);
+                    self.bump_clause(index);
+                    self.assign(asserting, Some(index));
+                }
+                self.var_increment /= VAR_DECAY;
+                self.clause_increment /= CLAUSE_DECAY;
+            } else {
+                if conflicts_here >= conflict_budget {
+                    self.backtrack(0);
+                    return None;
+                }
+                if self.num_learnts as f64 >= self.max_learnts + self.trail.len() as f64 {
+                    self.reduce_learnts();
+                    self.max_learnts *= 1.1;
+                }
+                match self.pick_branch_var() {
+                    None => return Some(true), // 모든 변수가 할당됨
+                    Some(var) => {
+                        self.decisions += 1;
+                        self.trail_limits.push(self.trail.len());
+                        let lit = Lit::new(var, !self.saved_phase[var]);
+                        self.assign(lit, None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    fn lit_value(&self, lit: Lit) -> Value {
+        match (self.values[lit.var()], lit.is_negated()) {
+            (Value::Unassigned, _) => Value::Unassigned,
+            (Value::True, false) | (Value::False, true) => Value::True,
+            _ => Value::False,
+        }
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.values[var] = if lit.is_negated() { Value::False } else { Value::True };
+        self.levels[var] = self.decision_level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    fn attach_clause(&mut self, lits: Vec<Lit>, learnt: bool) -> usize {
+        let index = self.clauses.len();
+        if learnt {
+            self.num_learnts += 1;
+        }
+        self.watches[lits[0].code()].push(index);
+        self.watches[lits[1].code()].push(index);
+        self.clauses.push(ClauseData { lits, learnt, activity: 0.0 });
+        index
+    }
+
+    // 단위 전파. 충돌한 절이 있으면 그 번호를 반환
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagate_head < self.trail.len() {
+            let false_lit = !self.trail[self.propagate_head];
+            self.propagate_head += 1;
+            self.propagations += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit.code()]);
+            let mut i = 0;
+            let mut conflict = None;
+            while i < watchers.len() {
+                let index = watchers[i];
+
+                // 거짓이 된 리터럴을 두 번째 자리로 옮김
+                if self.clauses[index].lits[0] == false_lit {
+                    self.clauses[index].lits.swap(0, 1);
+                }
+                let first = self.clauses[index].lits[0];
+                if self.lit_value(first) == Value::True {
+                    i += 1;
+                    continue;
+                }
+
+                // 새로 감시할 리터럴 찾기
+                let replacement = (2..self.clauses[index].lits.len())
+                    .find(|&k| self.lit_value(self.clauses[index].lits[k]) != Value::False);
+                if let Some(k) = replacement {
+                    self.clauses[index].lits.swap(1, k);
+                    let new_watch = self.clauses[index].lits[1];
+                    self.watches[new_watch.code()].push(index);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+
+                // 남은 리터럴이 하나뿐: 단위 절이거나 충돌
+                if self.lit_value(first) == Value::False {
+                    conflict = Some(index);
+                    break;
+                }
+                self.assign(first, Some(index));
+                i += 1;
+            }
+            self.watches[false_lit.code()].append(&mut watchers);
+            if conflict.is_some() {
+                self.propagate_head = self.trail.len();
+                return conflict;
+            }
+        }
+        None
+    }
+
+    // 1-UIP 충돌 분석. 학습 절(첫 리터럴이 단언 리터럴)과 되돌아갈 레벨을 반환
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt: Vec<Lit> = vec![Lit::new(0, false)]; // 0번 자리는 UIP용
+        let mut path_count = 0;
+        let mut index = self.trail.len();
+        let mut reason = Some(conflict);
+        let mut uip = None;
+
+        loop {
+            let clause_index = reason.expect("non-decision literal has a reason");
+            self.bump_clause(clause_index);
+            let lits = self.clauses[clause_index].lits.clone();
+            let skip_first = uip.is_some();
+            for &lit in lits.iter().skip(skip_first as usize) {
+                let var = lit.var();
+                if !self.seen[var] && self.levels[var] > 0 {
+                    self.seen[var] = true;
+                    self.bump_var(var);
+                    if self.levels[var] >= self.decision_level() {
+                        path_count += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            // trail을 거슬러 올라가며 다음으로 볼 리터럴 선택
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            self.seen[lit.var()] = false;
+            reason = self.reasons[lit.var()];
+            uip = Some(lit);
+            path_count -= 1;
+            if path_count == 0 {
+                break;
+            }
+        }
+        learnt[0] = !uip.unwrap();
+
+        // 학습 절 최소화: 다른 리터럴에 의해 함의되는 리터럴 제거
+        let mut minimized = vec![learnt[0]];
+        for &lit in &learnt[1..] {
+            let redundant = match self.reasons[lit.var()] {
+                None => false,
+                Some(r) => self.clauses[r].lits.iter().all(|&other| {
+                    other.var() == lit.var() || self.seen[other.var()] || self.levels[other.var()] == 0
+                }),
+            };
+            if !redundant {
+                minimized.push(lit);
+            }
+        }
+        for &lit in &learnt {
+            self.seen[lit.var()] = false;
+        }
+
+        // 두 번째로 높은 레벨의 리터럴을 1번 자리로 (감시 리터럴이 됨)
+        let mut backtrack_level = 0;
+        if minimized.len() > 1 {
+            let mut max_index = 1;
+            for k in 2..minimized.len() {
+                if self.levels[minimized[k].var()] > self.levels[minimized[max_index].var()] {
+                    max_index = k;
+                }
+            }
+            minimized.swap(1, max_index);
+            backtrack_level = self.levels[minimized[1].var()];
+        }
+        (minimized, backtrack_level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let limit = self.trail_limits[level];
+        for k in (limit..self.trail.len()).rev() {
+            let lit = self.trail[k];
+            let var = lit.var();
+            self.saved_phase[var] = !lit.is_negated();
+            self.values[var] = Value::Unassigned;
+            self.reasons[var] = None;
+            if !self.order.contains(var) {
+                self.order.insert(var, &self.activity);
+            }
+        }
+        self.trail.truncate(limit);
+        self.trail_limits.truncate(level);
+        self.propagate_head = limit;
+    }
+
+    fn pick_branch_var(&mut self) -> Option<usize> {
+        while let Some(var) = self.order.pop_max(&self.activity) {
+            if self.values[var] == Value::Unassigned {
+                return Some(var);
+            }
+        }
+        None
+    }
+
+    fn bump_var(&mut self, var: usize) {
+        self.activity[var] += self.var_increment;
+        if self.activity[var] > 1e100 {
+            // 값이 너무 커지면 전체를 축소
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+            self.var_increment *= 1e-100;
+        }
+        if self.order.contains(var) {
+            self.order.increase(var, &self.activity);
+        }
+    }
+
+    fn bump_clause(&mut self, index: usize) {
+        if !self.clauses[index].learnt {
+            return;
+        }
+        self.clauses[index].activity += self.clause_increment;
+        if self.clauses[index].activity > 1e20 {
+            for clause in self.clauses.iter_mut().filter(|c| c.learnt) {
+                clause.activity *= 1e-20;
+            }
+            self.clause_increment *= 1e-20;
+        }
+    }
+
+    // 활동도가 낮은 학습 절의 절반을 삭제. 현재 함의의 이유가 되는 절과 이진 절은 유지
+    // 삭제한 절은 바로 제거하고, 남은 절의 번호를 감시 목록과 이유에서 다시 매김
+    fn reduce_learnts(&mut self) {
+        let mut candidates: Vec<usize> = (0..self.clauses.len())
+            .filter(|&i| self.clauses[i].learnt && self.clauses[i].lits.len() > 2)
+            .filter(|&i| !self.is_locked(i))
+            .collect();
+        candidates.sort_by(|&a, &b| self.clauses[a].activity.total_cmp(&self.clauses[b].activity));
+        let mut removed = vec![false; self.clauses.len()];
+        for &index in candidates.iter().take(candidates.len() / 2) {
+            removed[index] = true;
+            self.num_learnts -= 1;
+        }
+
+        let mut remap = vec![None; self.clauses.len()];
+        let mut next = 0;
+        for (index, &gone) in removed.iter().enumerate() {
+            if !gone {
+                remap[index] = Some(next);
+                next += 1;
+            }
+        }
+        let mut index = 0;
+        self.clauses.retain(|_| {
+            index += 1;
+            !removed[index - 1]
+        });
+        for watchers in self.watches.iter_mut() {
+            *watchers = watchers.iter().filter_map(|&index| remap[index]).collect();
+        }
+        for reason in self.reasons.iter_mut() {
+            *reason = reason.and_then(|index| remap[index]);
+        }
+    }
+
+    fn is_locked(&self, index: usize) -> bool {
+        let first = self.clauses[index].lits[0];
+        self.reasons[first.var()] == Some(index) && self.lit_value(first) == Value::True
+    }
+}
+
+// Luby 재시작 수열: 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, ...
+fn luby(mut index: u64) -> u64 {
+    let mut size = 1;
+    let mut sequence = 0;
+    while size < index + 1 {
+        sequence += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != index {
+        size = (size - 1) >> 1;
+        sequence -= 1;
+        index %= size;
+    }
+    1 << sequence
+}
+
+// 활동도 기준 최대 힙 (VSIDS 분기 순서)
+struct VarHeap {
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+}
+
+impl VarHeap {
+    fn new(num_vars: usize) -> VarHeap {
+        VarHeap { heap: Vec::with_capacity(num_vars), positions: vec![None; num_vars] }
+    }
+
+    fn contains(&self, var: usize) -> bool {
+        self.positions[var].is_some()
+    }
+
+    fn insert(&mut self, var: usize, activity: &[f64]) {
+        self.positions[var] = Some(self.heap.len());
+        self.heap.push(var);
+        self.sift_up(self.heap.len() - 1, activity);
+    }
+
+    fn increase(&mut self, var: usize, activity: &[f64]) {
+        if let Some(position) = self.positions[var] {
+            self.sift_up(position, activity);
+        }
+    }
+
+    fn pop_max(&mut self, activity: &[f64]) -> Option<usize> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let top = self.heap.swap_remove(0);
+        self.positions[top] = None;
+        if !self.heap.is_empty() {
+            self.positions[self.heap[0]] = Some(0);
+            self.sift_down(0, activity);
+        }
+        Some(top)
+    }
+
+    fn sift_up(&mut self, mut position: usize, activity: &[f64]) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if activity[self.heap[parent]] >= activity[self.heap[position]] {
+                break;
+            }
+            self.swap(parent, position);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize, activity: &[f64]) {
+        loop {
+            let left = 2 * position + 1;
+            let right = left + 1;
+            let mut largest = position;
+            if left < self.heap.len() && activity[self.heap[left]] > activity[self.heap[largest]] {
+                largest = left;
+            }
+            if right < self.heap.len() && activity[self.heap[right]] > activity[self.heap[largest]] {
+                largest = right;
+            }
+            if largest == position {
+                break;
+            }
+            self.swap(position, largest);
+            position = largest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = Some(a);
+        self.positions[self.heap[b]] = Some(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(dimacs: i32) -> Lit {
+        Lit::new(dimacs.unsigned_abs() as usize - 1, dimacs < 0)
+    }
+
+    fn solve(num_vars: usize, clauses: &[Vec<i32>]) -> Option<Vec<bool>> {
+        let mut solver = Solver::new(num_vars);
+        for clause in clauses {
+            let clause: Vec<Lit> = clause.iter().map(|&l| lit(l)).collect();
+            solver.add_clause(&clause);
+        }
+        solver.solve()
+    }
+
+    fn satisfies(model: &[bool], clauses: &[Vec<i32>]) -> bool {
+        clauses.iter().all(|clause| clause.iter().any(|&l| model[l.unsigned_abs() as usize - 1] == (l > 0)))
+    }
+
+    // 비둘기집 원리: n+1마리 비둘기를 n개의 집에 넣을 수 없음
+    fn pigeonhole(holes: usize) -> (usize, Vec<Vec<i32>>) {
+        let pigeons = holes + 1;
+        let var = |p: usize, h: usize| (p * holes + h + 1) as i32;
+        let mut clauses = Vec::new();
+        for p in 0..pigeons {
+            clauses.push((0..holes).map(|h| var(p, h)).collect());
+        }
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    clauses.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        (pigeons * holes, clauses)
+    }
+
+    #[test]
+    fn test_solver_basic() {
+        assert!(solve(1, &[vec![1]]).is_some());
+        assert!(solve(1, &[vec![1], vec![-1]]).is_none());
+        assert!(solve(2, &[vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]]).is_none());
+        assert_eq!(solve(2, &[vec![1, 2], vec![-1], vec![]]), None);
+        assert!(solve(0, &[]).is_some());
+
+        let clauses = vec![vec![1, 2, 3], vec![-1, -2], vec![-2, -3], vec![-1, -3], vec![2, 3]];
+        let model = solve(3, &clauses).unwrap();
+        assert!(satisfies(&model, &clauses));
+
+        for holes in 2..=6 {
+            let (num_vars, clauses) = pigeonhole(holes);
+            assert!(solve(num_vars, &clauses).is_none(), "pigeonhole {}", holes);
+        }
+    }
+
+    #[test]
+    fn test_reduce_learnts() {
+        // 학습 절이 많이 생기는 문제: 삭제된 절은 절 목록과 감시 목록에서 실제로 사라짐
+        let (num_vars, clauses) = pigeonhole(7);
+        let mut solver = Solver::new(num_vars);
+        for clause in &clauses {
+            solver.add_clause(&clause.iter().map(|&l| lit(l)).collect::<Vec<_>>());
+        }
+        solver.max_learnts = 50.0;
+        assert_eq!(solver.search(u64::MAX), Some(false));
+        assert!(solver.conflicts > 1000);
+        let learnts = solver.clauses.iter().filter(|clause| clause.learnt).count();
+        assert_eq!(learnts, solver.num_learnts);
+        assert!(learnts < solver.conflicts as usize / 2);
+        let watched: usize = solver.watches.iter().map(Vec::len).sum();
+        assert_eq!(watched, 2 * solver.clauses.len());
+        assert!(solver.reasons.iter().flatten().all(|&index| index < solver.clauses.len()));
+    }
+
+    #[test]
+    fn test_solver_random_3sat() {
+        // 결정적인 의사 난수로 만든 3-SAT 문제를 전수 조사 결과와 비교
+        let mut seed: u64 = 0x2545F4914F6CDD1D;
+        let mut next = |bound: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+        for _ in 0..200 {
+            let num_vars = 3 + next(8) as usize;
+            let num_clauses = num_vars * 4 + next(6) as usize;
+            let clauses: Vec<Vec<i32>> = (0..num_clauses)
+                .map(|_| {
+                    (0..3)
+                        .map(|_| {
+                            let var = next(num_vars as u64) as i32 + 1;
+                            if next(2) == 0 { var } else { -var }
+                        })
+                        .collect()
+                })
+                .collect();
+            let brute_force = (0..1u64 << num_vars).any(|row| {
+                let model: Vec<bool> = (0..num_vars).map(|v| row >> v & 1 == 1).collect();
+                satisfies(&model, &clauses)
+            });
+            match solve(num_vars, &clauses) {
+                Some(model) => {
+                    assert!(brute_force);
+                    assert!(satisfies(&model, &clauses));
+                }
+                None => assert!(!brute_force),
+            }
+        }
+
+        // 상전이 근처의 큰 문제: 찾은 모델이 실제로 모든 절을 만족하는지 확인
+        for _ in 0..10 {
+            let num_vars = 80;
+            let clauses: Vec<Vec<i32>> = (0..340)
+                .map(|_| {
+                    (0..3)
+                        .map(|_| {
+                            let var = next(num_vars as u64) as i32 + 1;
+                            if next(2) == 0 { var } else { -var }
+                        })
+                        .collect()
+                })
+                .collect();
+            if let Some(model) = solve(num_vars, &clauses) {
+                assert!(satisfies(&model, &clauses));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solver_many_variables() {
+        // x1 -> x2 -> ... -> x200 함의 사슬: 31개를 넘는 변수도 처리
+        let n = 200;
+        let mut clauses: Vec<Vec<i32>> = (1..n).map(|i| vec![-i, i + 1]).collect();
+        clauses.push(vec![1]);
+        let model = solve(n as usize, &clauses).unwrap();
+        assert!(model.iter().all(|&value| value));
+        clauses.push(vec![-n]);
+        assert!(solve(n as usize, &clauses).is_none());
+    }
+
+    #[test]
+    fn test_luby() {
+        let sequence: Vec<u64> = (0..15).map(luby).collect();
+        assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+}
@@ -1,78 +1,148 @@
-use crate::ex03::ast::{ASTNode, get_ast};
-use crate::ex05::negation_normal_form::nnf;
-use crate::ex06::conjunctive_normal_form::cnf;
-use std::collections::HashSet;
+use crate::ex03::ast::{Expr, Notation, Op, parse_formula};
+use crate::ex03::error::FormulaError;
+use crate::ex03::boolean_evaluation::{evaluate_in, BooleanAlgebra};
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+use std::collections::{HashMap, HashSet};
 
 // eval_set 함수: 주어진 CNF 논리식을 집합에 적용하여 결과 반환
-pub fn eval_set(formula: &str, sets: &Vec<Vec<i32>>) -> Vec<i32> {
-    let ast = match get_ast(formula) {
-        Ok(ast) => ast,
-        Err(_) => {
-            eprintln!("Invalid formula");
-            return vec![];
+pub fn eval_set(formula: &str, sets: &[Vec<i32>]) -> Vec<i32> {
+    eval_set_with_notation(formula, sets, Notation::Postfix)
+}
+
+// 주어진 표기법으로 작성된 논리식을 집합에 적용하는 함수
+pub fn eval_set_with_notation(formula: &str, sets: &[Vec<i32>], notation: Notation) -> Vec<i32> {
+    match try_eval_set_with_notation(formula, sets, notation) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Invalid formula: {}", e);
+            vec![]
         }
-    };
+    }
+}
 
-    let nnf_ast = nnf(&ast);
-    let cnf_ast = cnf(&nnf_ast);
+// 집합 연산 결과를 반환하고, 실패하면 에러를 반환하는 함수
+pub fn try_eval_set(formula: &str, sets: &[Vec<i32>]) -> Result<Vec<i32>, FormulaError> {
+    try_eval_set_with_notation(formula, sets, Notation::Postfix)
+}
+
+pub fn try_eval_set_with_notation(formula: &str, sets: &[Vec<i32>], notation: Notation) -> Result<Vec<i32>, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+
+    // 'A', 'B', 'C' 또는 "x0", "x1" 같은 변수는 sets의 인덱스로 해석됨
+    // "A"와 "x0"처럼 서로 다른 변수가 같은 집합을 가리키면 에러
+    let mut bindings = HashMap::new();
+    let mut owners: HashMap<usize, Symbol> = HashMap::new();
+    for var in extract_and_sort_vars_from_ast(&ast) {
+        let Some(index) = var.index() else { continue };
+        if let Some(&owner) = owners.get(&index) {
+            return Err(FormulaError::AmbiguousSetIndex { index, first: owner.to_string(), second: var.to_string() });
+        }
+        owners.insert(index, var);
+        if let Some(set) = sets.get(index) {
+            bindings.insert(var, set.as_slice());
+        }
+    }
 
     // 전체 집합을 구하기 위해 모든 집합의 합집합을 구함
     let universal_set: HashSet<i32> = sets.iter().flat_map(|set| set.iter().cloned()).collect();
-
-    // 집합 연산을 수행하는 함수를 호출하여 결과 반환
-    eval_cnf_set(&cnf_ast, sets, &universal_set)
+    eval_bound_set(&ast, &bindings, &universal_set)
 }
 
-fn eval_cnf_set(ast: &ASTNode, sets: &Vec<Vec<i32>>, universal_set: &HashSet<i32>) -> Vec<i32> {
-    let result = match ast {
-        ASTNode::Operand(var) => {
-            // 'A', 'B', 'C' 같은 변수는 sets의 인덱스로 해석됨
-            let idx = (*var as usize) - ('A' as usize);
-            if idx < sets.len() {
-                sets[idx].clone()
-            } else {
-                vec![]
-            }
+// 이름으로 지정한 집합에 논리식을 적용하는 함수
+pub fn eval_set_named(formula: &str, sets: &HashMap<String, Vec<i32>>, notation: Notation) -> Vec<i32> {
+    match try_eval_set_named(formula, sets, notation) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Invalid formula: {}", e);
+            vec![]
         }
-        ASTNode::Operator('&', left, Some(right)) => {
-            let left_set = eval_cnf_set(left, sets, universal_set);
-            let right_set = eval_cnf_set(right, sets, universal_set);
-            intersection(&left_set, &right_set)
+    }
+}
+
+pub fn try_eval_set_named(formula: &str, sets: &HashMap<String, Vec<i32>>, notation: Notation) -> Result<Vec<i32>, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    let bindings = sets
+        .iter()
+        .map(|(name, set)| Ok((Symbol::try_intern(name)?, set.as_slice())))
+        .collect::<Result<_, FormulaError>>()?;
+    let universal_set: HashSet<i32> = sets.values().flat_map(|set| set.iter().cloned()).collect();
+    eval_bound_set(&ast, &bindings, &universal_set)
+}
+
+fn eval_bound_set(ast: &Expr, sets: &HashMap<Symbol, &[i32]>, universal_set: &HashSet<i32>) -> Result<Vec<i32>, FormulaError> {
+    // 집합 연산을 수행하는 영역에서 AST를 평가하여 결과 반환
+    let algebra = SetAlgebra { sets, universal_set };
+    let mut result = evaluate_in(ast, &algebra)?;
+    result.sort();  // 결과 집합을 정렬
+    Ok(result)
+}
+
+// 집합 영역: AND는 교집합, OR는 합집합, NOT은 전체 집합에 대한 보수
+struct SetAlgebra<'a> {
+    sets: &'a HashMap<Symbol, &'a [i32]>,
+    universal_set: &'a HashSet<i32>,
+}
+
+impl BooleanAlgebra for SetAlgebra<'_> {
+    type Value = Vec<i32>;
+
+    fn constant(&self, value: bool) -> Vec<i32> {
+        if value {
+            self.universal_set.iter().copied().collect()
+        } else {
+            vec![]
         }
-        ASTNode::Operator('|', left, Some(right)) => {
-            let left_set = eval_cnf_set(left, sets, universal_set);
-            let right_set = eval_cnf_set(right, sets, universal_set);
-            union(&left_set, &right_set)
+    }
+
+    fn variable(&self, var: Symbol) -> Result<Vec<i32>, FormulaError> {
+        match self.sets.get(&var) {
+            Some(set) => Ok(set.to_vec()),
+            None => Err(FormulaError::UnboundVariable(var.name().to_string())),
         }
-        ASTNode::Operator('!', operand, None) => {
-            let set = eval_cnf_set(operand, sets, universal_set);
-            complement(&set, universal_set)
+    }
+
+    fn not(&self, value: Vec<i32>) -> Vec<i32> {
+        complement(&value, self.universal_set)
+    }
+
+    fn binary(&self, op: Op, left_set: Vec<i32>, right_set: Vec<i32>) -> Vec<i32> {
+        match op {
+            Op::And => intersection(&left_set, &right_set),
+            Op::Or => union(&left_set, &right_set),
+            Op::Xor => union(&difference(&left_set, &right_set), &difference(&right_set, &left_set)),
+            Op::Implies => union(&complement(&left_set, self.universal_set), &right_set),
+            Op::Iff => complement(
+                &union(&difference(&left_set, &right_set), &difference(&right_set, &left_set)),
+                self.universal_set,
+            ),
         }
-        _ => vec![],
-    };
-    
-    let mut sorted_result = result.clone(); // 결과 복사
-    sorted_result.sort();  // 결과 집합을 정렬
-    sorted_result
+    }
 }
 
 
 // 집합의 교집합
-fn intersection(set1: &Vec<i32>, set2: &Vec<i32>) -> Vec<i32> {
+fn intersection(set1: &[i32], set2: &[i32]) -> Vec<i32> {
     let set1: HashSet<_> = set1.iter().collect();
     let set2: HashSet<_> = set2.iter().collect();
     set1.intersection(&set2).cloned().copied().collect()
 }
 
 // 집합의 합집합
-fn union(set1: &Vec<i32>, set2: &Vec<i32>) -> Vec<i32> {
+fn union(set1: &[i32], set2: &[i32]) -> Vec<i32> {
     let set1: HashSet<_> = set1.iter().collect();
     let set2: HashSet<_> = set2.iter().collect();
     set1.union(&set2).cloned().copied().collect()
 }
 
+// 집합의 차집합
+fn difference(set1: &[i32], set2: &[i32]) -> Vec<i32> {
+    let set2: HashSet<_> = set2.iter().collect();
+    set1.iter().filter(|value| !set2.contains(value)).copied().collect()
+}
+
 // 집합의 보수 (차집합)
-fn complement(set: &Vec<i32>, universal_set: &HashSet<i32>) -> Vec<i32> {
+fn complement(set: &[i32], universal_set: &HashSet<i32>) -> Vec<i32> {
     let set: HashSet<_> = set.iter().copied().collect();  // 참조에서 값을 복사하여 HashSet<i32> 생성
     universal_set.difference(&set).cloned().collect()     // 차집합 계산
 }
@@ -178,4 +248,53 @@ mod tests {
         let sets = vec![vec![1], vec![1], vec![1]];
         assert_eq!(eval_set("ABC&&", &sets), vec![1]);
     }
+
+    #[test]
+    fn eval_set_infix_tests() {
+        let sets = vec![vec![1, 2, 3], vec![2, 3, 4]];
+        assert_eq!(eval_set_with_notation("!A & B", &sets, Notation::Infix), vec![4]);
+        assert_eq!(eval_set_with_notation("A ∨ B", &sets, Notation::Infix), vec![1, 2, 3, 4]);
+        assert_eq!(eval_set_with_notation("A ⊕ B", &sets, Notation::Infix), vec![1, 4]);
+    }
+
+    #[test]
+    fn eval_set_errors() {
+        let sets = vec![vec![1, 2, 3], vec![2, 3, 4]];
+        assert_eq!(try_eval_set("AB&", &sets), Ok(vec![2, 3]));
+        assert_eq!(try_eval_set("AC&", &sets), Err(FormulaError::UnboundVariable("C".to_string())));
+        assert_eq!(try_eval_set("A&", &sets), Err(FormulaError::StackUnderflow { token: 1, operator: '&' }));
+        assert_eq!(eval_set("AC&", &sets), vec![]);
+        assert_eq!(try_eval_set("A1&", &sets), Ok(vec![1, 2, 3]));
+        assert_eq!(try_eval_set("A0|", &sets), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn eval_set_named_variables() {
+        // "x0", "x1"은 인덱스로 해석됨
+        let sets = vec![vec![1, 2, 3], vec![2, 3, 4]];
+        assert_eq!(eval_set("[x0][x1]!&", &sets), vec![1]);
+        assert_eq!(eval_set_with_notation("x0 & x1", &sets, Notation::Infix), vec![2, 3]);
+
+        // 같은 인덱스를 가리키는 서로 다른 변수는 섞어 쓸 수 없음
+        assert_eq!(try_eval_set("A[x1]&", &sets), Ok(vec![2, 3]));
+        assert_eq!(
+            try_eval_set("A[x0]&", &sets),
+            Err(FormulaError::AmbiguousSetIndex { index: 0, first: "A".to_string(), second: "x0".to_string() })
+        );
+        assert_eq!(
+            try_eval_set_with_notation("y1 | B", &sets, Notation::Infix),
+            Err(FormulaError::AmbiguousSetIndex { index: 1, first: "B".to_string(), second: "y1".to_string() })
+        );
+        assert_eq!(eval_set("[door0][x0]|", &sets), vec![]);
+
+        let mut named = HashMap::new();
+        named.insert("door_open".to_string(), vec![1, 2]);
+        named.insert("light_on".to_string(), vec![2, 5]);
+        assert_eq!(eval_set_named("door_open & !light_on", &named, Notation::Infix), vec![1]);
+        assert_eq!(eval_set_named("[door_open][light_on]|", &named, Notation::Postfix), vec![1, 2, 5]);
+        assert_eq!(
+            try_eval_set_named("door_open & window", &named, Notation::Infix),
+            Err(FormulaError::UnboundVariable("window".to_string()))
+        );
+    }
 }
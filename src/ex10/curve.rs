@@ -14,6 +14,7 @@ pub fn map(x: u16, y: u16) -> f64 {
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_range_contains)]
 mod tests {
     use super::*;
 
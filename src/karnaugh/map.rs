@@ -0,0 +1,226 @@
+use crate::ex02::gray_code::gray_code;
+use crate::ex03::ast::Expr;
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::bitset::TruthTable;
+use crate::minimize::cube::Cube;
+use crate::minimize::quine_mccluskey::minimum_cover;
+
+// 카르노 맵: 진리표를 Gray 코드 순서의 격자에 배치
+// 앞쪽 n / 2개 변수가 행, 나머지가 열. 이웃한 칸(양 끝 포함)은 변수 하나만 다름
+// 그룹은 모든 칸이 1인 직사각형 중 더 큰 직사각형에 포함되지 않는 것 (양 끝을 넘어 이어질 수 있음)
+
+pub const MAX_KARNAUGH_VARIABLES: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KarnaughMap {
+    table: TruthTable,
+    row_bits: usize,
+    col_bits: usize,
+}
+
+// 격자의 직사각형. 행과 열은 시작 위치와 길이이며 끝을 넘으면 처음으로 이어짐
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+    pub row: usize,
+    pub rows: usize,
+    pub col: usize,
+    pub cols: usize,
+    pub cube: Cube, // 그룹이 나타내는 곱항 (변수 i는 비트 n - 1 - i)
+}
+
+impl Group {
+    pub fn contains_cell(&self, row: usize, col: usize, num_rows: usize, num_cols: usize) -> bool {
+        (row + num_rows - self.row) % num_rows < self.rows && (col + num_cols - self.col) % num_cols < self.cols
+    }
+}
+
+impl KarnaughMap {
+    pub fn from_ast(ast: &Expr) -> Result<KarnaughMap, FormulaError> {
+        KarnaughMap::from_table(TruthTable::from_ast(ast)?)
+    }
+
+    pub fn from_table(table: TruthTable) -> Result<KarnaughMap, FormulaError> {
+        let n = table.num_vars();
+        if n > MAX_KARNAUGH_VARIABLES {
+            return Err(FormulaError::TooManyVariables { count: n, limit: MAX_KARNAUGH_VARIABLES });
+        }
+        Ok(KarnaughMap { table, row_bits: n / 2, col_bits: n - n / 2 })
+    }
+
+    pub fn vars(&self) -> &[Symbol] {
+        self.table.vars()
+    }
+
+    pub fn row_vars(&self) -> &[Symbol] {
+        &self.vars()[..self.row_bits]
+    }
+
+    pub fn col_vars(&self) -> &[Symbol] {
+        &self.vars()[self.row_bits..]
+    }
+
+    pub fn num_rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn num_cols(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    // 행 위치의 Gray 코드 (행 변수의 값)
+    pub fn row_code(&self, row: usize) -> usize {
+        gray_code(row as u32) as usize
+    }
+
+    pub fn col_code(&self, col: usize) -> usize {
+        gray_code(col as u32) as usize
+    }
+
+    // 칸에 해당하는 진리표의 행 번호
+    pub fn table_row(&self, row: usize, col: usize) -> usize {
+        self.row_code(row) << self.col_bits | self.col_code(col)
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> bool {
+        self.table.get(self.table_row(row, col))
+    }
+
+    // 모든 최대 그룹. 큰 그룹부터
+    pub fn groups(&self) -> Vec<Group> {
+        let row_ranges = ranges(self.row_bits);
+        let col_ranges = ranges(self.col_bits);
+        let mut candidates: Vec<Group> = Vec::new();
+        for &(row, rows, row_cube) in &row_ranges {
+            for &(col, cols, col_cube) in &col_ranges {
+                let all_ones = (0..rows).all(|i| {
+                    (0..cols).all(|j| self.cell((row + i) % self.num_rows(), (col + j) % self.num_cols()))
+                });
+                if !all_ones {
+                    continue;
+                }
+                let cube = Cube {
+                    value: row_cube.value << self.col_bits | col_cube.value,
+                    care: row_cube.care << self.col_bits | col_cube.care,
+                };
+                if !candidates.iter().any(|group| group.cube == cube) {
+                    candidates.push(Group { row, rows, col, cols, cube });
+                }
+            }
+        }
+
+        let mut groups: Vec<Group> = candidates
+            .iter()
+            .filter(|group| !candidates.iter().any(|other| other.cube != group.cube && other.cube.contains(&group.cube)))
+            .copied()
+            .collect();
+        groups.sort_by_key(|group| (group.cube.literal_count(), group.row, group.col, group.rows, group.cols));
+        groups
+    }
+
+    // 모든 1을 덮는 가장 작은 그룹 집합
+    pub fn cover(&self) -> Vec<Group> {
+        let groups = self.groups();
+        let cubes: Vec<Cube> = groups.iter().map(|group| group.cube).collect();
+        let minterms: Vec<u64> = (0..self.table.len()).filter(|&row| self.table.get(row)).map(|row| row as u64).collect();
+        let chosen = minimum_cover(&cubes, &minterms);
+        groups.into_iter().filter(|group| chosen.contains(&group.cube)).collect()
+    }
+
+    // 그룹이 나타내는 곱항
+    pub fn group_expr(&self, group: &Group) -> Expr {
+        group.cube.to_expr(self.vars())
+    }
+}
+
+// 한 축에서 곱항이 되는 구간: 길이가 2의 거듭제곱이고 Gray 코드 값들이 부분 큐브를 이루는 것
+// (시작, 길이, 그 구간의 곱항)
+fn ranges(bits: usize) -> Vec<(usize, usize, Cube)> {
+    let size = 1usize << bits;
+    let mut ranges = Vec::new();
+    for k in 0..=bits {
+        let len = 1usize << k;
+        // 축 전체는 시작 위치와 상관없이 같은 구간
+        let starts = if len == size { 1 } else { size };
+        for start in 0..starts {
+            let codes: Vec<u64> = (0..len).map(|i| gray_code(((start + i) % size) as u32) as u64).collect();
+            let all = codes.iter().fold(u64::MAX, |acc, &code| acc & code);
+            let any = codes.iter().fold(0, |acc, &code| acc | code);
+            let varying = all ^ any;
+            if varying.count_ones() as usize == k {
+                let care = ((1u64 << bits) - 1) & !varying;
+                ranges.push((start, len, Cube { value: all & care, care }));
+            }
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    fn map(formula: &str) -> KarnaughMap {
+        KarnaughMap::from_ast(&parse_expr(formula).unwrap()).unwrap()
+    }
+
+    // 식에 나타나지 않는 변수도 격자에 포함
+    fn map_over(formula: &str, vars: &str) -> KarnaughMap {
+        let vars: Vec<Symbol> = vars.chars().map(Symbol::from).collect();
+        KarnaughMap::from_table(TruthTable::from_ast_with_vars(&parse_expr(formula).unwrap(), &vars).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_layout() {
+        let kmap = map("ABCD&&&");
+        assert_eq!((kmap.num_rows(), kmap.num_cols()), (4, 4));
+        assert_eq!(kmap.row_vars(), &[Symbol::from('A'), Symbol::from('B')]);
+        // Gray 순서 00 01 11 10: ABCD=1111은 세 번째 행, 세 번째 열
+        assert!(kmap.cell(2, 2));
+        assert_eq!(kmap.table_row(2, 2), 15);
+        assert_eq!(kmap.table_row(3, 1), 0b1001);
+        assert_eq!((0..4).filter(|&r| (0..4).any(|c| kmap.cell(r, c))).count(), 1);
+
+        let kmap = map("ABC||");
+        assert_eq!((kmap.num_rows(), kmap.num_cols()), (2, 4));
+        assert_eq!(map("A").num_cols(), 2);
+        let wide = parse_expr("ABCDEFG||||||").unwrap();
+        assert_eq!(KarnaughMap::from_ast(&wide), Err(FormulaError::TooManyVariables { count: 7, limit: 6 }));
+    }
+
+    #[test]
+    fn test_groups() {
+        let exprs = |kmap: &KarnaughMap, groups: Vec<Group>| -> Vec<String> {
+            groups.iter().map(|group| kmap.group_expr(group).to_string()).collect()
+        };
+
+        // 네 모서리: !B & !D (양 끝을 넘는 그룹)
+        let kmap = map_over("B!D!&", "ABCD");
+        let groups = kmap.groups();
+        assert_eq!(exprs(&kmap, groups.clone()), vec!["B!D!&"]);
+        assert_eq!((groups[0].row, groups[0].rows, groups[0].col, groups[0].cols), (3, 2, 3, 2));
+        assert!(groups[0].contains_cell(0, 0, 4, 4) && groups[0].contains_cell(3, 3, 4, 4));
+        assert!(!groups[0].contains_cell(1, 0, 4, 4));
+
+        // 순환하는 주항 6개 중 3개로 덮음
+        let kmap = map("A!B!&A!C!&|B!C&|AB&|AC!&B&|");
+        assert_eq!(kmap.groups().len(), 6);
+        assert_eq!(kmap.cover().len(), 3);
+
+        // 최대 그룹만: AB | C에서 C는 한 행 전체가 아닌 열 두 개
+        let kmap = map("AB&C|");
+        assert_eq!(exprs(&kmap, kmap.groups()), vec!["C", "AB&"]);
+        assert_eq!(exprs(&kmap, map("AA!|").groups()), vec!["1"]);
+        assert!(map("AA!&").groups().is_empty());
+
+        // 6개 변수: 같은 Gray 구간이라도 부분 큐브가 아닌 구간은 그룹이 아님
+        let kmap = map("ABCDEF^^^^^");
+        assert_eq!(kmap.groups().len(), 32);
+        assert!(kmap.groups().iter().all(|group| group.rows == 1 && group.cols == 1));
+        let kmap = map_over("AD&", "ABCDEF");
+        let groups = kmap.groups();
+        assert_eq!(exprs(&kmap, groups.clone()), vec!["AD&"]);
+        assert_eq!((groups[0].rows, groups[0].cols), (4, 4));
+    }
+}
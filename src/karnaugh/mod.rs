@@ -0,0 +1,2 @@
+pub mod map;
+pub mod render;
\ No newline at end of file
@@ -0,0 +1,229 @@
+use std::fmt::Write;
+use crate::ex03::ast::{expr_to_infix_string, parse_formula, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use super::map::{Group, KarnaughMap};
+
+// 카르노 맵을 텍스트와 SVG로 출력
+// 그룹마다 a, b, c, ... 이름을 붙이고, 칸에는 값 뒤에 그 칸을 덮는 그룹 이름을 표시
+//
+//  A\BC   00   01   11   10
+//     0    0   1a   1a    0
+//     1    0   1a  1ab   1b
+//
+// a: C
+// b: A & B
+
+const CELL: usize = 48; // SVG 칸 크기
+const MARGIN: usize = 64; // SVG 왼쪽/위쪽 제목 영역
+const COLORS: [&str; 6] = ["#d62728", "#1f77b4", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b"];
+
+fn group_name(index: usize) -> char {
+    const NAMES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    NAMES[index % NAMES.len()] as char
+}
+
+// "AB", 여러 글자 이름이 있으면 "x1,door"
+fn joined_names(vars: &[Symbol]) -> String {
+    let separator = if vars.iter().all(|var| var.is_single_letter()) { "" } else { "," };
+    vars.iter().map(|var| var.name()).collect::<Vec<_>>().join(separator)
+}
+
+fn code_label(code: usize, bits: usize) -> String {
+    if bits == 0 { String::new() } else { format!("{:0width$b}", code, width = bits) }
+}
+
+fn axis_bits(map: &KarnaughMap) -> (usize, usize) {
+    (map.row_vars().len(), map.col_vars().len())
+}
+
+fn cell_text(map: &KarnaughMap, groups: &[Group], row: usize, col: usize) -> String {
+    let mut text = String::from(if map.cell(row, col) { "1" } else { "0" });
+    for (index, group) in groups.iter().enumerate() {
+        if group.contains_cell(row, col, map.num_rows(), map.num_cols()) {
+            text.push(group_name(index));
+        }
+    }
+    text
+}
+
+// 그룹 목록의 범례: "a: C"
+fn legend(map: &KarnaughMap, groups: &[Group]) -> Vec<String> {
+    groups
+        .iter()
+        .enumerate()
+        .map(|(index, group)| format!("{}: {}", group_name(index), expr_to_infix_string(&map.group_expr(group))))
+        .collect()
+}
+
+pub fn render_text(map: &KarnaughMap, groups: &[Group]) -> String {
+    let (row_bits, col_bits) = axis_bits(map);
+    let corner = format!("{}\\{}", joined_names(map.row_vars()), joined_names(map.col_vars()));
+    let cells: Vec<Vec<String>> = (0..map.num_rows())
+        .map(|row| (0..map.num_cols()).map(|col| cell_text(map, groups, row, col)).collect())
+        .collect();
+    let label_width = corner.chars().count().max(row_bits);
+    let cell_width = cells.iter().flatten().map(String::len).max().unwrap_or(1).max(col_bits);
+
+    let mut output = format!(" {:>width$}", corner, width = label_width);
+    for col in 0..map.num_cols() {
+        let _ = write!(output, "  {:>width$}", code_label(map.col_code(col), col_bits), width = cell_width);
+    }
+    output.push('\n');
+    for (row, row_cells) in cells.iter().enumerate() {
+        let _ = write!(output, " {:>width$}", code_label(map.row_code(row), row_bits), width = label_width);
+        for cell in row_cells {
+            let _ = write!(output, "  {:>width$}", cell, width = cell_width);
+        }
+        output.push('\n');
+    }
+    if !groups.is_empty() {
+        output.push('\n');
+        for line in legend(map, groups) {
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+// 순환 구간을 격자 안의 연속 구간들로 나눔
+fn segments(start: usize, len: usize, size: usize) -> Vec<(usize, usize)> {
+    if start + len <= size {
+        vec![(start, len)]
+    } else {
+        vec![(start, size - start), (0, start + len - size)]
+    }
+}
+
+pub fn render_svg(map: &KarnaughMap, groups: &[Group]) -> String {
+    let (row_bits, col_bits) = axis_bits(map);
+    let (rows, cols) = (map.num_rows(), map.num_cols());
+    let width = MARGIN + cols * CELL + 16;
+    let legend_lines = legend(map, groups);
+    let height = MARGIN + rows * CELL + 16 + legend_lines.len() * 20;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"16\">\n",
+        w = width,
+        h = height
+    );
+    let corner = format!("{}\\{}", joined_names(map.row_vars()), joined_names(map.col_vars()));
+    let _ = writeln!(svg, "<text x=\"4\" y=\"20\">{}</text>", xml_escape(&corner));
+    for col in 0..cols {
+        let x = MARGIN + col * CELL + CELL / 2;
+        let label = code_label(map.col_code(col), col_bits);
+        let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>", x, MARGIN - 8, label);
+    }
+    for row in 0..rows {
+        let y = MARGIN + row * CELL + CELL / 2 + 6;
+        let label = code_label(map.row_code(row), row_bits);
+        let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>", MARGIN - 8, y, label);
+    }
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let (x, y) = (MARGIN + col * CELL, MARGIN + row * CELL);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\"/>",
+                x, y, CELL, CELL
+            );
+            let value = if map.cell(row, col) { 1 } else { 0 };
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                x + CELL / 2,
+                y + CELL / 2 + 6,
+                value
+            );
+        }
+    }
+
+    // 끝을 넘는 그룹은 조각마다 둥근 사각형을 그림. 겹치는 그룹이 구분되도록 안쪽 여백을 다르게 둠
+    for (index, group) in groups.iter().enumerate() {
+        let color = COLORS[index % COLORS.len()];
+        let inset = 3 + 4 * (index % 4);
+        for (row, height) in segments(group.row, group.rows, rows) {
+            for (col, width) in segments(group.col, group.cols, cols) {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"10\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                    MARGIN + col * CELL + inset,
+                    MARGIN + row * CELL + inset,
+                    width * CELL - 2 * inset,
+                    height * CELL - 2 * inset,
+                    color
+                );
+            }
+        }
+    }
+
+    for (index, line) in legend_lines.iter().enumerate() {
+        let y = MARGIN + rows * CELL + 32 + index * 20;
+        let color = COLORS[index % COLORS.len()];
+        let _ = writeln!(svg, "<text x=\"4\" y=\"{}\" fill=\"{}\">{}</text>", y, color, xml_escape(line));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// 주어진 표기법으로 읽어 최소 피복 그룹과 함께 텍스트 카르노 맵을 반환
+pub fn try_karnaugh_text(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let map = KarnaughMap::from_ast(&parse_formula(formula, notation)?)?;
+    Ok(render_text(&map, &map.cover()))
+}
+
+pub fn try_karnaugh_svg(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let map = KarnaughMap::from_ast(&parse_formula(formula, notation)?)?;
+    Ok(render_svg(&map, &map.cover()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_text() {
+        assert_eq!(
+            try_karnaugh_text("AB&C|", Notation::Postfix).unwrap(),
+            " A\\BC   00   01   11   10\n    0    0   1a   1a    0\n    1    0   1a  1ab   1b\n\na: C\nb: A & B\n"
+        );
+        // 네 모서리 그룹
+        assert_eq!(
+            try_karnaugh_text("!B & !D | A & B & C & D & !A", Notation::Infix).unwrap(),
+            " AB\\CD  00  01  11  10\n    00  1a   0   0  1a\n    01   0   0   0   0\n    11   0   0   0   0\n    10  1a   0   0  1a\n\na: !B & !D\n"
+        );
+        assert_eq!(try_karnaugh_text("AA!&", Notation::Postfix).unwrap(), " \\A  0  1\n     0  0\n");
+        assert_eq!(try_karnaugh_text("1", Notation::Postfix).unwrap(), " \\    \n    1a\n\na: 1\n");
+        assert_eq!(
+            try_karnaugh_text("door | x1", Notation::Infix).unwrap().lines().next(),
+            Some(" door\\x1    0    1")
+        );
+        assert_eq!(
+            try_karnaugh_text("ABCDEFG||||||", Notation::Postfix),
+            Err(FormulaError::TooManyVariables { count: 7, limit: 6 })
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let svg = try_karnaugh_svg("!B & !D | A & B & C & D & !A", Notation::Infix).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // 4x4 칸과 네 모서리로 나뉜 그룹 조각 4개
+        assert_eq!(svg.matches("stroke=\"black\"").count(), 16);
+        assert_eq!(svg.matches("rx=\"10\"").count(), 4);
+        assert!(svg.contains(">a: !B &amp; !D</text>"));
+        assert!(svg.contains(">AB\\CD</text>"));
+
+        let svg = try_karnaugh_svg("AB&C|", Notation::Postfix).unwrap();
+        assert_eq!(svg.matches("rx=\"10\"").count(), 2);
+        assert_eq!(svg.matches("<text").count(), 1 + 4 + 2 + 8 + 2);
+    }
+}
@@ -9,4 +9,8 @@ pub mod ex07;
 pub mod ex08;
 pub mod ex09;
 pub mod ex10;
-pub mod ex11;
\ No newline at end of file
+pub mod ex11;
+pub mod minimize;
+pub mod bdd;
+pub mod karnaugh;
+pub mod analysis;
\ No newline at end of file
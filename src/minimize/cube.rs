@@ -0,0 +1,187 @@
+use crate::ex03::ast::Expr;
+use crate::ex03::symbol::Symbol;
+
+// 곱항(cube): care 비트가 1인 변수만 나타나고, 그 값은 value 비트
+// 변수 i는 비트 (n - 1 - i)에 대응하여 진리표의 행 번호와 같은 순서 (첫 번째 변수가 최상위 비트)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cube {
+    pub value: u64,
+    pub care: u64,
+}
+
+pub const MAX_VARIABLES: usize = 64;
+
+// 하위 n비트가 1인 마스크
+pub fn full_mask(num_vars: usize) -> u64 {
+    if num_vars >= 64 { u64::MAX } else { (1u64 << num_vars) - 1 }
+}
+
+impl Cube {
+    // 모든 변수가 나타나는 최소항
+    pub fn minterm(row: u64, num_vars: usize) -> Cube {
+        Cube { value: row, care: full_mask(num_vars) }
+    }
+
+    // 항상 참인 빈 곱항
+    pub fn universe() -> Cube {
+        Cube { value: 0, care: 0 }
+    }
+
+    pub fn covers(&self, row: u64) -> bool {
+        row & self.care == self.value
+    }
+
+    // self가 other를 포함하는지 (other의 모든 최소항이 self에도 속함)
+    pub fn contains(&self, other: &Cube) -> bool {
+        self.care & !other.care == 0 && other.value & self.care == self.value
+    }
+
+    // 두 곱항에 공통인 최소항이 있는지
+    pub fn intersects(&self, other: &Cube) -> bool {
+        (self.value ^ other.value) & self.care & other.care == 0
+    }
+
+    pub fn literal_count(&self) -> u32 {
+        self.care.count_ones()
+    }
+
+    // 한 변수의 값만 다른 두 곱항을 합침: A!B + AB -> A
+    pub fn merge(&self, other: &Cube) -> Option<Cube> {
+        let difference = self.value ^ other.value;
+        if self.care != other.care || difference.count_ones() != 1 {
+            return None;
+        }
+        Some(Cube { value: self.value & !difference, care: self.care & !difference })
+    }
+
+    pub fn to_expr(&self, vars: &[Symbol]) -> Expr {
+        let n = vars.len();
+        let mut literals = vars.iter().enumerate().filter_map(|(i, &var)| {
+            let bit = 1u64 << (n - 1 - i);
+            if self.care & bit == 0 {
+                None
+            } else if self.value & bit == 0 {
+                Some(Expr::negate(Expr::Var(var)))
+            } else {
+                Some(Expr::Var(var))
+            }
+        });
+        match literals.next() {
+            Some(first) => literals.fold(first, Expr::and),
+            None => Expr::Const(true),
+        }
+    }
+}
+
+// 곱항들의 OR. DNF와 같이 왼쪽으로 중첩
+pub fn cover_to_expr(cover: &[Cube], vars: &[Symbol]) -> Expr {
+    let mut terms = cover.iter().map(|cube| cube.to_expr(vars));
+    match terms.next() {
+        Some(first) => terms.fold(first, Expr::or),
+        None => Expr::Const(false),
+    }
+}
+
+// cube로 제한했을 때의 피복: cube와 겹치는 곱항에서 cube의 변수를 없앰
+pub fn cofactor(cover: &[Cube], cube: &Cube) -> Vec<Cube> {
+    cover
+        .iter()
+        .filter(|other| other.intersects(cube))
+        .map(|other| Cube { value: other.value & !cube.care, care: other.care & !cube.care })
+        .collect()
+}
+
+// 피복이 항상 참인지 확인. 양쪽 극성으로 나타나는 변수로 나누어 재귀적으로 검사
+pub fn is_tautology(cover: &[Cube]) -> bool {
+    let mut cover = cover.to_vec();
+    loop {
+        if cover.iter().any(|cube| cube.care == 0) {
+            return true;
+        }
+        if cover.is_empty() {
+            return false;
+        }
+        // 한 가지 극성으로만 나타나는(unate) 변수는 불리한 쪽 값으로 고정해도 결과가 같으므로
+        // 그 변수를 포함하는 곱항을 제거
+        let positive = cover.iter().fold(0, |acc, cube| acc | (cube.care & cube.value));
+        let negative = cover.iter().fold(0, |acc, cube| acc | (cube.care & !cube.value));
+        let unate = positive ^ negative;
+        if unate == 0 {
+            break;
+        }
+        cover.retain(|cube| cube.care & unate == 0);
+    }
+
+    // 가장 많은 곱항에 나타나는 양극성(binate) 변수로 나눔
+    let mut best: Option<(usize, u64)> = None;
+    let mut bits = cover.iter().fold(0, |acc, cube| acc | cube.care);
+    while bits != 0 {
+        let bit = bits & bits.wrapping_neg();
+        bits &= bits - 1;
+        let count = cover.iter().filter(|cube| cube.care & bit != 0).count();
+        if best.is_none_or(|(best_count, _)| count > best_count) {
+            best = Some((count, bit));
+        }
+    }
+    let (_, bit) = best.expect("a cover without unate variables has a binate variable");
+    is_tautology(&cofactor(&cover, &Cube { value: bit, care: bit }))
+        && is_tautology(&cofactor(&cover, &Cube { value: 0, care: bit }))
+}
+
+// cube의 모든 최소항이 피복에 속하는지
+pub fn cover_contains(cover: &[Cube], cube: &Cube) -> bool {
+    is_tautology(&cofactor(cover, cube))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    #[test]
+    fn test_cube() {
+        // 변수 A, B, C: A!C = 1?0
+        let cube = Cube { value: 0b100, care: 0b101 };
+        assert!(cube.covers(0b100) && cube.covers(0b110));
+        assert!(!cube.covers(0b101) && !cube.covers(0b000));
+        assert_eq!(cube.literal_count(), 2);
+        assert!(cube.contains(&Cube::minterm(0b110, 3)));
+        assert!(Cube::universe().contains(&cube));
+        assert!(!cube.contains(&Cube::universe()));
+        assert!(cube.intersects(&Cube { value: 0b010, care: 0b010 }));
+        assert!(!cube.intersects(&Cube { value: 0b001, care: 0b001 }));
+
+        assert_eq!(Cube::minterm(0b100, 3).merge(&Cube::minterm(0b110, 3)), Some(cube));
+        assert_eq!(Cube::minterm(0b100, 3).merge(&Cube::minterm(0b111, 3)), None);
+        assert_eq!(cube.merge(&Cube::minterm(0b000, 3)), None);
+
+        let vars = [Symbol::from('A'), Symbol::from('B'), Symbol::from('C')];
+        assert_eq!(cube.to_expr(&vars), parse_expr("AC!&").unwrap());
+        assert_eq!(Cube::universe().to_expr(&vars), Expr::Const(true));
+        assert_eq!(cover_to_expr(&[cube, Cube { value: 0b010, care: 0b010 }], &vars), parse_expr("AC!&B|").unwrap());
+        assert_eq!(cover_to_expr(&[], &vars), Expr::Const(false));
+        assert_eq!(full_mask(3), 0b111);
+        assert_eq!(full_mask(64), u64::MAX);
+    }
+
+    #[test]
+    fn test_tautology() {
+        // A | !A
+        let a = Cube { value: 0b10, care: 0b10 };
+        let not_a = Cube { value: 0b00, care: 0b10 };
+        assert!(is_tautology(&[a, not_a]));
+        assert!(!is_tautology(&[a]));
+        assert!(!is_tautology(&[]));
+        assert!(is_tautology(&[Cube::universe()]));
+
+        // A&B | A&!B | !A
+        let cover = [Cube { value: 0b11, care: 0b11 }, Cube { value: 0b10, care: 0b11 }, not_a];
+        assert!(is_tautology(&cover));
+        assert!(!is_tautology(&cover[..2]));
+
+        // A&B | !A&C 는 B&C를 포함
+        let cover = [Cube { value: 0b110, care: 0b110 }, Cube { value: 0b001, care: 0b101 }];
+        assert!(cover_contains(&cover, &Cube { value: 0b011, care: 0b011 }));
+        assert!(!cover_contains(&cover, &Cube { value: 0b010, care: 0b010 }));
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use crate::ex03::ast::{ast_to_string, parse_formula, Expr, Notation, Op};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::extract_and_sort_vars_from_ast;
+use crate::ex05::negation_normal_form::nnf;
+use super::cube::{cover_contains, cover_to_expr, Cube, MAX_VARIABLES};
+
+// Espresso 방식의 휴리스틱 2단 최소화
+// 진리표 대신 곱항 피복(cover) 위에서 expand / irredundant / reduce를 비용이 줄지 않을 때까지 반복
+// 곱항이 함수에 포함되는지는 원래 피복에 대한 tautology 검사로 확인하므로 OFF-set이 필요 없음
+
+// 식을 곱항으로 펼칠 때 허용하는 최대 곱항 수
+pub const MAX_COVER_CUBES: usize = 4096;
+
+// on: 함수가 1인 곱항들, dont_care: 값이 상관없는 곱항들
+pub fn espresso(on: &[Cube], dont_care: &[Cube]) -> Vec<Cube> {
+    // 함수 전체(ON-set과 don't care)를 나타내는 기준 피복
+    let reference: Vec<Cube> = on.iter().chain(dont_care).copied().collect();
+
+    let mut cover = expand(on.to_vec(), &reference);
+    cover = irredundant(cover, dont_care);
+    let mut best_cost = cost(&cover);
+    loop {
+        let candidate = irredundant(expand(reduce(cover.clone(), dont_care), &reference), dont_care);
+        let candidate_cost = cost(&candidate);
+        if candidate_cost >= best_cost {
+            break;
+        }
+        cover = candidate;
+        best_cost = candidate_cost;
+    }
+    cover.sort();
+    cover
+}
+
+// (곱항 수, 리터럴 수)
+fn cost(cover: &[Cube]) -> (usize, u32) {
+    (cover.len(), cover.iter().map(Cube::literal_count).sum())
+}
+
+// 각 곱항에서 리터럴을 하나씩 지워 보며 함수 안에 머무는 한 최대한 키움
+// 키운 곱항에 포함되는 다른 곱항은 제거
+fn expand(mut cover: Vec<Cube>, reference: &[Cube]) -> Vec<Cube> {
+    // 큰 곱항(리터럴이 적은 것)부터 처리하여 작은 곱항이 흡수되도록 함
+    cover.sort_by_key(|cube| (cube.literal_count(), *cube));
+    let mut expanded: Vec<Cube> = Vec::new();
+    for cube in cover {
+        if expanded.iter().any(|larger| larger.contains(&cube)) {
+            continue;
+        }
+        let mut current = cube;
+        let mut bits = cube.care;
+        while bits != 0 {
+            let bit = bits & bits.wrapping_neg();
+            bits &= bits - 1;
+            let raised = Cube { value: current.value & !bit, care: current.care & !bit };
+            if cover_contains(reference, &raised) {
+                current = raised;
+            }
+        }
+        expanded.retain(|smaller| !current.contains(smaller));
+        expanded.push(current);
+    }
+    expanded
+}
+
+// 나머지 곱항과 don't care로 덮이는 곱항을 제거. 리터럴이 많은(작은) 곱항부터 검사
+fn irredundant(mut cover: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    cover.sort_by_key(|cube| (std::cmp::Reverse(cube.literal_count()), *cube));
+    let mut index = 0;
+    while index < cover.len() {
+        let others: Vec<Cube> = cover
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != index)
+            .map(|(_, cube)| *cube)
+            .chain(dont_care.iter().copied())
+            .collect();
+        if cover_contains(&others, &cover[index]) {
+            cover.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    cover
+}
+
+// 다른 곱항이 이미 덮고 있는 부분을 떼어 내어 곱항을 줄임. 다음 expand에서 다른 방향으로 키울 수 있게 함
+fn reduce(mut cover: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    for index in 0..cover.len() {
+        let others: Vec<Cube> = cover
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != index)
+            .map(|(_, cube)| *cube)
+            .chain(dont_care.iter().copied())
+            .collect();
+        let mut current = cover[index];
+        let mut free = !current.care & full_care(&cover, dont_care);
+        while free != 0 {
+            let bit = free & free.wrapping_neg();
+            free &= free - 1;
+            // 한쪽 절반이 다른 곱항으로 덮이면 나머지 절반만 남김
+            let high = Cube { value: current.value | bit, care: current.care | bit };
+            let low = Cube { value: current.value, care: current.care | bit };
+            if cover_contains(&others, &high) {
+                current = low;
+            } else if cover_contains(&others, &low) {
+                current = high;
+            }
+        }
+        cover[index] = current;
+    }
+    cover
+}
+
+// 피복에 나타나는 모든 변수
+fn full_care(cover: &[Cube], dont_care: &[Cube]) -> u64 {
+    cover.iter().chain(dont_care).fold(0, |acc, cube| acc | cube.care)
+}
+
+// 식을 곱항 피복으로 변환 (변수 i는 비트 n - 1 - i)
+// NNF 위에서 AND를 OR 위로 분배하므로 CNF 모양의 식은 곱항 수가 지수적으로 늘어남
+// 도중에 MAX_COVER_CUBES개를 넘으면 TooManyTerms
+pub fn cover_from_expr(ast: &Expr, vars: &[Symbol]) -> Result<Vec<Cube>, FormulaError> {
+    if vars.len() > MAX_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_VARIABLES });
+    }
+    let bits: HashMap<Symbol, u64> =
+        vars.iter().enumerate().map(|(i, &var)| (var, 1u64 << (vars.len() - 1 - i))).collect();
+    cover_of(&nnf(ast), &bits)
+}
+
+fn cover_of(ast: &Expr, bits: &HashMap<Symbol, u64>) -> Result<Vec<Cube>, FormulaError> {
+    let mut cover = match ast {
+        Expr::Const(true) => vec![Cube::universe()],
+        Expr::Const(false) => vec![],
+        Expr::Var(var) => vec![literal_cube(*var, false, bits)?],
+        Expr::Not(operand) => match &**operand {
+            Expr::Var(var) => vec![literal_cube(*var, true, bits)?],
+            Expr::Const(value) => return cover_of(&Expr::Const(!value), bits),
+            _ => return cover_of(&nnf(ast), bits),
+        },
+        Expr::Binary(Op::Or, left, right) => {
+            let mut cover = cover_of(left, bits)?;
+            cover.extend(cover_of(right, bits)?);
+            cover
+        }
+        Expr::Binary(Op::And, left, right) => {
+            let left = cover_of(left, bits)?;
+            let right = cover_of(right, bits)?;
+            let mut cover = Vec::new();
+            for a in &left {
+                // A & !A 같은 모순 곱항은 버림
+                for b in right.iter().filter(|b| a.intersects(b)) {
+                    cover.push(Cube { value: a.value | b.value, care: a.care | b.care });
+                    if cover.len() > MAX_COVER_CUBES {
+                        return Err(FormulaError::TooManyTerms { limit: MAX_COVER_CUBES });
+                    }
+                }
+            }
+            cover
+        }
+        Expr::Binary(_, _, _) => return cover_of(&nnf(ast), bits),
+    };
+    cover = absorb(cover);
+    if cover.len() > MAX_COVER_CUBES {
+        return Err(FormulaError::TooManyTerms { limit: MAX_COVER_CUBES });
+    }
+    Ok(cover)
+}
+
+// 다른 곱항에 포함되는 곱항 제거: A | A & B -> A
+fn absorb(mut cover: Vec<Cube>) -> Vec<Cube> {
+    cover.sort_by_key(|cube| (cube.literal_count(), *cube));
+    let mut kept: Vec<Cube> = Vec::new();
+    for cube in cover {
+        if !kept.iter().any(|larger| larger.contains(&cube)) {
+            kept.push(cube);
+        }
+    }
+    kept
+}
+
+fn literal_cube(var: Symbol, negated: bool, bits: &HashMap<Symbol, u64>) -> Result<Cube, FormulaError> {
+    let bit = *bits.get(&var).ok_or_else(|| FormulaError::UnboundVariable(var.name().to_string()))?;
+    Ok(Cube { value: if negated { 0 } else { bit }, care: bit })
+}
+
+// 논리식을 휴리스틱으로 최소화한 곱의 합
+pub fn espresso_minimize(ast: &Expr) -> Result<Expr, FormulaError> {
+    espresso_minimize_with_dont_cares(ast, &Expr::Const(false))
+}
+
+pub fn espresso_minimize_with_dont_cares(ast: &Expr, dont_care: &Expr) -> Result<Expr, FormulaError> {
+    let vars = extract_and_sort_vars_from_ast(&Expr::and(ast.clone(), dont_care.clone()));
+    let on = cover_from_expr(ast, &vars)?;
+    let dont_care_cubes = cover_from_expr(dont_care, &vars)?;
+    let cover = espresso(&on, &dont_care_cubes);
+
+    // 진리표 없이 곱항 단위로 검증: 결과의 곱항은 ON-set과 don't care 안에 있고,
+    // ON-set의 곱항은 결과와 don't care로 덮여야 함
+    let reference: Vec<Cube> = on.iter().chain(&dont_care_cubes).copied().collect();
+    let result: Vec<Cube> = cover.iter().chain(&dont_care_cubes).copied().collect();
+    let minimized = cover_to_expr(&cover, &vars);
+    if !cover.iter().all(|cube| cover_contains(&reference, cube)) || !on.iter().all(|cube| cover_contains(&result, cube)) {
+        return Err(FormulaError::InvalidModel(minimized.to_string()));
+    }
+    Ok(minimized)
+}
+
+/// 주어진 표기법으로 읽고, 최소화한 결과를 같은 표기법으로 반환
+pub fn try_espresso_formula(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&espresso_minimize(&ast)?, notation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+    use crate::minimize::quine_mccluskey::{equivalent_on_care_set, minimize};
+
+    fn var(name: String) -> Expr {
+        Expr::Var(Symbol::intern(&name))
+    }
+
+    #[test]
+    fn test_espresso_small() {
+        let minimize_postfix = |formula: &str| try_espresso_formula(formula, Notation::Postfix).unwrap();
+        assert_eq!(minimize_postfix("AB&AB!&|"), "A");
+        assert_eq!(minimize_postfix("AB&A!C&|BC&|"), "A!C&AB&|");
+        assert_eq!(minimize_postfix("AB^"), "A!B&AB!&|");
+        assert_eq!(minimize_postfix("AA!|"), "1");
+        assert_eq!(minimize_postfix("AA!&"), "0");
+
+        // don't care
+        let ast = parse_expr("AB&").unwrap();
+        assert_eq!(espresso_minimize_with_dont_cares(&ast, &parse_expr("AB!&").unwrap()).unwrap(), parse_expr("A").unwrap());
+
+        // 작은 식에서는 정확한 최소화와 같은 비용
+        for formula in ["AB=CD=^", "AB>C>D|", "AB&CD&|AC&|", "AB!&C|D^A!&", "ABC&&A!B&C&|AB!&C&|"] {
+            let ast = parse_expr(formula).unwrap();
+            let heuristic = espresso_minimize(&ast).unwrap();
+            let exact = minimize(&ast).unwrap();
+            assert_eq!(equivalent_on_care_set(&ast, &heuristic, &Expr::Const(false)), Ok(true), "{}", formula);
+            let count = |expr: &Expr| {
+                let vars = extract_and_sort_vars_from_ast(&ast);
+                cover_from_expr(expr, &vars).unwrap().len()
+            };
+            assert_eq!(count(&heuristic), count(&exact), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_espresso_many_variables() {
+        // 30개 변수: (a_i & b_i) | (a_i & !b_i) -> a_i
+        let mut ast = Expr::Const(false);
+        for i in 0..15 {
+            let a = var(format!("a{}", i));
+            let b = var(format!("b{}", i));
+            ast = Expr::or(ast, Expr::or(Expr::and(a.clone(), b.clone()), Expr::and(a, Expr::negate(b))));
+        }
+        let minimized = espresso_minimize(&ast).unwrap();
+        let vars = extract_and_sort_vars_from_ast(&ast);
+        let cover = cover_from_expr(&minimized, &vars).unwrap();
+        assert_eq!(cover.len(), 15);
+        assert!(cover.iter().all(|cube| cube.literal_count() == 1));
+
+        // 60개 변수: 합의항(consensus) b_i & c_i는 중복이므로 제거
+        let mut ast = Expr::Const(false);
+        for i in 0..20 {
+            let a = var(format!("a{}", i));
+            let b = var(format!("b{}", i));
+            let c = var(format!("c{}", i));
+            let group = Expr::or(
+                Expr::or(Expr::and(a.clone(), b.clone()), Expr::and(Expr::negate(a), c.clone())),
+                Expr::and(b, c),
+            );
+            ast = Expr::or(ast, group);
+        }
+        let vars = extract_and_sort_vars_from_ast(&ast);
+        assert_eq!(vars.len(), 60);
+        let cover = cover_from_expr(&espresso_minimize(&ast).unwrap(), &vars).unwrap();
+        assert_eq!(cover.len(), 40);
+        assert!(cover.iter().all(|cube| cube.literal_count() == 2));
+    }
+
+    #[test]
+    fn test_espresso_cnf_input() {
+        // CNF 모양: (a_i | b_i)의 AND. 곱의 합은 2^n개 항이 필요
+        let pairs = |count: usize| {
+            (0..count)
+                .map(|i| Expr::or(var(format!("a{}", i)), var(format!("b{}", i))))
+                .reduce(Expr::and)
+                .unwrap()
+        };
+        let ast = pairs(6);
+        let vars = extract_and_sort_vars_from_ast(&ast);
+        let cover = cover_from_expr(&espresso_minimize(&ast).unwrap(), &vars).unwrap();
+        assert_eq!(cover.len(), 64);
+        assert!(cover.iter().all(|cube| cube.literal_count() == 6));
+        // 곱항이 너무 많아지면 펼치는 도중에 멈추고 에러
+        assert_eq!(espresso_minimize(&pairs(13)), Err(FormulaError::TooManyTerms { limit: MAX_COVER_CUBES }));
+        assert_eq!(espresso_minimize(&pairs(15)), Err(FormulaError::TooManyTerms { limit: MAX_COVER_CUBES }));
+        assert_eq!(espresso_minimize(&pairs(30)), Err(FormulaError::TooManyTerms { limit: MAX_COVER_CUBES }));
+
+        // 30개 변수의 CNF: (a_i | b_i) & (a_i | !b_i)는 흡수되어 a_i만 남음
+        let ast = (0..15)
+            .map(|i| {
+                let (a, b) = (var(format!("a{}", i)), var(format!("b{}", i)));
+                Expr::and(Expr::or(a.clone(), b.clone()), Expr::or(a, Expr::negate(b)))
+            })
+            .reduce(Expr::and)
+            .unwrap();
+        let vars = extract_and_sort_vars_from_ast(&ast);
+        assert_eq!(vars.len(), 30);
+        let cover = cover_from_expr(&espresso_minimize(&ast).unwrap(), &vars).unwrap();
+        assert_eq!(cover.len(), 1);
+        assert_eq!(cover[0].literal_count(), 15);
+        assert_eq!(
+            cover_from_expr(&parse_expr("AB|").unwrap(), &[Symbol::from('A')]),
+            Err(FormulaError::UnboundVariable("B".to_string()))
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cube;
+pub mod quine_mccluskey;
+pub mod espresso;
\ No newline at end of file
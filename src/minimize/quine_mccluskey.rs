@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+use crate::ex03::ast::{ast_to_string, parse_formula, Expr, Notation};
+use crate::ex03::error::FormulaError;
+use crate::ex03::symbol::Symbol;
+use crate::ex04::truth_table::{evaluate_truth_table, extract_and_sort_vars_from_ast};
+use super::cube::{cover_to_expr, Cube};
+
+// Quine-McCluskey로 주항(prime implicant)을 구하고 Petrick 방법으로 최소 피복을 고르는 정확한 최소화
+// 진리표 전체를 다루므로 변수 수를 제한
+
+pub const MAX_EXACT_VARIABLES: usize = 16;
+
+// 최소항과 don't care를 합쳐 더 이상 합칠 수 없는 곱항(주항)을 모두 구함
+// don't care만 포함하는 주항은 제외
+pub fn prime_implicants(num_vars: usize, minterms: &[u64], dont_cares: &[u64]) -> Vec<Cube> {
+    let mut current: HashSet<Cube> = minterms
+        .iter()
+        .chain(dont_cares)
+        .map(|&row| Cube::minterm(row, num_vars))
+        .collect();
+    let mut primes = Vec::new();
+
+    while !current.is_empty() {
+        let mut next = HashSet::new();
+        let mut merged = HashSet::new();
+        for cube in &current {
+            // 값이 한 비트만 다른 짝이 있으면 그 변수를 없앤 곱항을 만듦
+            let mut bits = cube.care;
+            while bits != 0 {
+                let bit = bits & bits.wrapping_neg();
+                bits &= bits - 1;
+                let partner = Cube { value: cube.value ^ bit, care: cube.care };
+                if current.contains(&partner) {
+                    next.insert(Cube { value: cube.value & !bit, care: cube.care & !bit });
+                    merged.insert(*cube);
+                }
+            }
+        }
+        primes.extend(current.iter().filter(|cube| !merged.contains(cube)).copied());
+        current = next;
+    }
+
+    primes.retain(|prime| minterms.iter().any(|&row| prime.covers(row)));
+    primes.sort();
+    primes
+}
+
+// 모든 최소항을 덮는 주항의 최소 집합 (곱항 수, 리터럴 수 순으로 최소)
+pub fn minimum_cover(primes: &[Cube], minterms: &[u64]) -> Vec<Cube> {
+    let mut minterms: Vec<u64> = minterms.to_vec();
+    minterms.sort();
+    minterms.dedup();
+
+    // 필수 주항: 어떤 최소항을 덮는 유일한 주항
+    let mut chosen: Vec<Cube> = Vec::new();
+    for &row in &minterms {
+        let covering: Vec<&Cube> = primes.iter().filter(|prime| prime.covers(row)).collect();
+        if let [only] = covering.as_slice() {
+            if !chosen.contains(only) {
+                chosen.push(**only);
+            }
+        }
+    }
+
+    let remaining: Vec<u64> = minterms
+        .into_iter()
+        .filter(|&row| !chosen.iter().any(|cube| cube.covers(row)))
+        .collect();
+    if !remaining.is_empty() {
+        let candidates: Vec<Cube> = primes
+            .iter()
+            .filter(|prime| !chosen.contains(prime) && remaining.iter().any(|&row| prime.covers(row)))
+            .copied()
+            .collect();
+        chosen.extend(petrick(&candidates, &remaining));
+    }
+    chosen.sort();
+    chosen
+}
+
+// Petrick 방법: 각 최소항을 덮는 후보의 합을 모두 곱해 전개하고, 가장 작은 곱항을 선택
+fn petrick(candidates: &[Cube], minterms: &[u64]) -> Vec<Cube> {
+    let words = candidates.len().div_ceil(64);
+    let mut products: Vec<Vec<u64>> = vec![vec![0; words]];
+
+    for &row in minterms {
+        let sum: Vec<usize> = (0..candidates.len()).filter(|&i| candidates[i].covers(row)).collect();
+        let mut expanded = Vec::new();
+        for product in &products {
+            if sum.iter().any(|&i| has(product, i)) {
+                expanded.push(product.clone());
+                continue;
+            }
+            for &i in &sum {
+                let mut next = product.clone();
+                next[i / 64] |= 1 << (i % 64);
+                expanded.push(next);
+            }
+        }
+        products = absorb(expanded);
+    }
+
+    let cost = |product: &Vec<u64>| {
+        let selected = (0..candidates.len()).filter(|&i| has(product, i));
+        let literals: u32 = selected.clone().map(|i| candidates[i].literal_count()).sum();
+        (selected.count(), literals)
+    };
+    let best = products
+        .iter()
+        .min_by(|a, b| cost(a).cmp(&cost(b)).then_with(|| a.cmp(b)))
+        .expect("every minterm is covered by at least one prime implicant");
+    (0..candidates.len()).filter(|&i| has(best, i)).map(|i| candidates[i]).collect()
+}
+
+fn has(product: &[u64], index: usize) -> bool {
+    product[index / 64] >> (index % 64) & 1 == 1
+}
+
+// 흡수 법칙: X + XY = X. 다른 곱항을 포함하는 곱항을 제거
+fn absorb(mut products: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+    let size = |product: &Vec<u64>| product.iter().map(|word| word.count_ones()).sum::<u32>();
+    products.sort_by_key(size);
+    products.dedup();
+    let mut kept: Vec<Vec<u64>> = Vec::new();
+    for product in products {
+        let absorbed = kept
+            .iter()
+            .any(|smaller| smaller.iter().zip(&product).all(|(small, large)| small & !large == 0));
+        if !absorbed {
+            kept.push(product);
+        }
+    }
+    kept
+}
+
+// 진리표(최소항과 don't care의 행 번호)를 최소 곱의 합으로 변환
+pub fn minimize_table(vars: &[Symbol], minterms: &[u64], dont_cares: &[u64]) -> Result<Expr, FormulaError> {
+    if vars.len() > MAX_EXACT_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_EXACT_VARIABLES });
+    }
+    let primes = prime_implicants(vars.len(), minterms, dont_cares);
+    Ok(cover_to_expr(&minimum_cover(&primes, minterms), vars))
+}
+
+// 논리식을 최소 곱의 합으로 변환
+pub fn minimize(ast: &Expr) -> Result<Expr, FormulaError> {
+    minimize_with_dont_cares(ast, &Expr::Const(false))
+}
+
+// dont_care가 참인 입력에서는 결과가 어느 값이어도 되는 최소화
+pub fn minimize_with_dont_cares(ast: &Expr, dont_care: &Expr) -> Result<Expr, FormulaError> {
+    let vars = extract_and_sort_vars_from_ast(&Expr::and(ast.clone(), dont_care.clone()));
+    if vars.len() > MAX_EXACT_VARIABLES {
+        return Err(FormulaError::TooManyVariables { count: vars.len(), limit: MAX_EXACT_VARIABLES });
+    }
+    let results = evaluate_truth_table(ast, &vars)?;
+    let dont_care_rows = evaluate_truth_table(dont_care, &vars)?;
+
+    let mut minterms = Vec::new();
+    let mut dont_cares = Vec::new();
+    for (row, (&result, &ignored)) in results.iter().zip(&dont_care_rows).enumerate() {
+        if ignored {
+            dont_cares.push(row as u64);
+        } else if result {
+            minterms.push(row as u64);
+        }
+    }
+
+    let minimized = minimize_table(&vars, &minterms, &dont_cares)?;
+    if !equivalent_on_care_set(ast, &minimized, dont_care)? {
+        return Err(FormulaError::InvalidModel(minimized.to_string()));
+    }
+    Ok(minimized)
+}
+
+// dont_care가 거짓인 모든 입력에서 두 식의 값이 같은지 진리표 평가로 확인
+pub fn equivalent_on_care_set(left: &Expr, right: &Expr, dont_care: &Expr) -> Result<bool, FormulaError> {
+    let all = Expr::and(Expr::and(left.clone(), right.clone()), dont_care.clone());
+    let vars = extract_and_sort_vars_from_ast(&all);
+    let left_rows = evaluate_truth_table(left, &vars)?;
+    let right_rows = evaluate_truth_table(right, &vars)?;
+    let dont_care_rows = evaluate_truth_table(dont_care, &vars)?;
+    Ok((0..left_rows.len()).all(|row| dont_care_rows[row] || left_rows[row] == right_rows[row]))
+}
+
+/// 주어진 표기법으로 읽고, 최소화한 결과를 같은 표기법으로 반환
+pub fn try_minimize_formula(formula: &str, notation: Notation) -> Result<String, FormulaError> {
+    let ast = parse_formula(formula, notation)?;
+    Ok(ast_to_string(&minimize(&ast)?, notation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex03::ast::parse_expr;
+
+    fn vars(names: &str) -> Vec<Symbol> {
+        names.chars().map(Symbol::from).collect()
+    }
+
+    #[test]
+    fn test_prime_implicants() {
+        // f(A,B,C) = m(0,1,2,5,6,7): 주항 6개가 순환하는 경우
+        let primes = prime_implicants(3, &[0, 1, 2, 5, 6, 7], &[]);
+        assert_eq!(primes.len(), 6);
+        assert!(primes.iter().all(|prime| prime.literal_count() == 2));
+
+        // don't care만 덮는 주항은 제외
+        let primes = prime_implicants(2, &[0], &[3]);
+        assert_eq!(primes, vec![Cube { value: 0, care: 0b11 }]);
+
+        // Petrick으로 3개짜리 피복을 선택
+        let cover = minimum_cover(&prime_implicants(3, &[0, 1, 2, 5, 6, 7], &[]), &[0, 1, 2, 5, 6, 7]);
+        assert_eq!(cover.len(), 3);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let minimize_postfix = |formula: &str| try_minimize_formula(formula, Notation::Postfix).unwrap();
+        assert_eq!(minimize_postfix("AB&AB!&|"), "A");
+        assert_eq!(minimize_postfix("AB|A&"), "A");
+        assert_eq!(minimize_postfix("AB^"), "A!B&AB!&|");
+        assert_eq!(minimize_postfix("AB>"), "A!B|");
+        assert_eq!(minimize_postfix("AA!|"), "1");
+        assert_eq!(minimize_postfix("AA!&"), "0");
+        assert_eq!(minimize_postfix("AB&C!&AB&C&|A!B&C&|"), "BC&AB&|");
+        assert_eq!(
+            try_minimize_formula("a & b & c | a & b & !c | !a & b & c", Notation::Infix),
+            Ok("b & c | a & b".to_string())
+        );
+
+        // f(A,B,C,D) = m(4,8,10,11,12,15) + d(9,14) -> B&!C&!D | A&!D | A&C (A&!B 대신 A&!D도 같은 비용)
+        let expr = minimize_table(&vars("ABCD"), &[4, 8, 10, 11, 12, 15], &[9, 14]).unwrap();
+        assert_eq!(expr, parse_expr("BC!&D!&AD!&|AC&|").unwrap());
+
+        // don't care를 식으로 지정
+        let ast = parse_expr("AB&").unwrap();
+        let dont_care = parse_expr("AB!&").unwrap();
+        assert_eq!(minimize_with_dont_cares(&ast, &dont_care).unwrap(), parse_expr("A").unwrap());
+        assert_eq!(equivalent_on_care_set(&ast, &parse_expr("A").unwrap(), &dont_care), Ok(true));
+        assert_eq!(equivalent_on_care_set(&ast, &parse_expr("A").unwrap(), &Expr::Const(false)), Ok(false));
+
+        // 모든 4변수 식 일부에 대해 결과가 원래 식과 동치
+        for formula in ["AB=CD=^", "AB>C>D|", "ABCD^^^", "AB&CD&|AC&|", "AB!&C|D^A!&"] {
+            let ast = parse_expr(formula).unwrap();
+            let minimized = minimize(&ast).unwrap();
+            assert_eq!(equivalent_on_care_set(&ast, &minimized, &Expr::Const(false)), Ok(true), "{}", formula);
+        }
+
+        let wide: Vec<Symbol> = (0..17).map(|i| Symbol::intern(&format!("x{}", i))).collect();
+        assert_eq!(minimize_table(&wide, &[], &[]), Err(FormulaError::TooManyVariables { count: 17, limit: 16 }));
+    }
+}